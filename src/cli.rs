@@ -0,0 +1,237 @@
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::stats::Format;
+use crate::storage::{Profiles, StorageError};
+
+/// Overrides the data directory when `--data-dir` is not given.
+pub const DATA_DIR_ENV: &str = "MEMORYKATA_DATA_DIR";
+/// `sqlite` keeps progress and the full answer history in SQLite.
+pub const BACKEND_ENV: &str = "MEMORYKATA_BACKEND";
+
+pub const USAGE: &str = "\
+Usage: memorykata [OPTIONS] [COMMAND]
+
+Without a command the MemoryKata window opens.
+
+Commands:
+  tui               Full-screen terminal interface with menu, games, settings and scoreboard
+  play <GAME>       Play a game in the terminal, answering on stdin
+                    (GAME is an id such as greek_alphabet or math_tricks)
+  stats             Print score, accuracy, streaks and due reviews per game
+
+Options:
+  --data-dir <DIR>  Where profiles and progress are kept
+                    (default: $MEMORYKATA_DATA_DIR, then the platform data directory)
+  --profile <NAME>  Profile to use (default: the one used last)
+  --seed <N>        Seed for a reproducible sequence of rounds
+  -h, --help        Print this help
+
+Play options:
+  --rounds <N>      How many rounds to play (default: 10)
+
+Stats options:
+  --format <FORMAT> table, json or csv (default: table)";
+
+/// Rounds played by `memorykata play` without `--rounds`.
+pub const DEFAULT_ROUNDS: usize = 10;
+
+/// A parsed command line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cli {
+    pub options: Options,
+    pub command: Command,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Open the egui window.
+    Window,
+    /// Full-screen terminal interface.
+    Tui,
+    /// Print per-game progress.
+    Stats(Format),
+    Play(PlayOptions),
+    Help,
+}
+
+/// Options shared by every command.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Directory given with `--data-dir`, if any.
+    pub data_dir: Option<PathBuf>,
+    /// Profile name or id given with `--profile`.
+    pub profile: Option<String>,
+    /// Seed every game's random source with this instead of entropy.
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PlayOptions {
+    /// [`GameEngine::id`](crate::games::GameEngine::id) of the game to play.
+    pub game: String,
+    pub rounds: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n\n{}", self.0, USAGE)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Parses the arguments that follow the program name. Options may appear
+/// before or after the command.
+pub fn parse(args: impl IntoIterator<Item = OsString>) -> Result<Cli, CliError> {
+    let mut options = Options::default();
+    let mut positional = Vec::new();
+    let mut rounds = None;
+    let mut format = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let Some(arg) = arg.to_str() else {
+            return Err(CliError(format!("unexpected argument {:?}", arg)));
+        };
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (arg, None),
+        };
+        match flag {
+            "-h" | "--help" => {
+                return Ok(Cli {
+                    options,
+                    command: Command::Help,
+                })
+            }
+            "--data-dir" => options.data_dir = Some(value(flag, inline, &mut args)?.into()),
+            "--profile" => options.profile = Some(text(flag, value(flag, inline, &mut args)?)?),
+            "--rounds" => rounds = Some(number(flag, value(flag, inline, &mut args)?)?),
+            "--seed" => options.seed = Some(number(flag, value(flag, inline, &mut args)?)?),
+            "--format" => {
+                let value = text(flag, value(flag, inline, &mut args)?)?;
+                format = Some(value.parse().map_err(CliError)?);
+            }
+            _ if flag.starts_with('-') => {
+                return Err(CliError(format!("unexpected option '{}'", flag)))
+            }
+            _ => positional.push(arg.to_string()),
+        }
+    }
+
+    let command = match positional.split_first() {
+        None => Command::Window,
+        Some((command, rest)) => match (command.as_str(), rest) {
+            ("tui", []) => Command::Tui,
+            ("stats", []) => Command::Stats(format.unwrap_or_default()),
+            ("play", [game]) => Command::Play(PlayOptions {
+                game: game.clone(),
+                rounds: rounds.unwrap_or(DEFAULT_ROUNDS),
+            }),
+            ("play", []) => return Err(CliError("play needs a game".into())),
+            ("tui" | "stats" | "play", [.., extra]) => {
+                return Err(CliError(format!("unexpected argument '{}'", extra)))
+            }
+            (command, _) => return Err(CliError(format!("unknown command '{}'", command))),
+        },
+    };
+    if rounds.is_some() && !matches!(command, Command::Play(_)) {
+        return Err(CliError("--rounds only applies to play".into()));
+    }
+    if format.is_some() && !matches!(command, Command::Stats(_)) {
+        return Err(CliError("--format only applies to stats".into()));
+    }
+    Ok(Cli { options, command })
+}
+
+/// The value of `flag`, either given inline as `--flag=value` or as the next argument.
+fn value(
+    flag: &str,
+    inline: Option<&str>,
+    args: &mut impl Iterator<Item = OsString>,
+) -> Result<OsString, CliError> {
+    inline
+        .map(OsString::from)
+        .or_else(|| args.next())
+        .ok_or_else(|| CliError(format!("{} needs a value", flag)))
+}
+
+fn text(flag: &str, value: OsString) -> Result<String, CliError> {
+    value
+        .into_string()
+        .map_err(|value| CliError(format!("{} got {:?}, which is not valid text", flag, value)))
+}
+
+fn number<T: FromStr>(flag: &str, value: OsString) -> Result<T, CliError> {
+    let value = text(flag, value)?;
+    value
+        .parse()
+        .map_err(|_| CliError(format!("{} expects a number, got '{}'", flag, value)))
+}
+
+impl Options {
+    /// The data directory to use: `--data-dir`, then `MEMORYKATA_DATA_DIR`,
+    /// then [`default_data_dir`].
+    pub fn data_dir(&self) -> PathBuf {
+        self.data_dir
+            .clone()
+            .or_else(|| {
+                env::var_os(DATA_DIR_ENV)
+                    .filter(|dir| !dir.is_empty())
+                    .map(PathBuf::from)
+            })
+            .unwrap_or_else(default_data_dir)
+    }
+
+    /// Opens the profiles in [`Options::data_dir`], playing as the one named
+    /// with `--profile` if given. That choice is not remembered for later runs.
+    pub fn profiles(&self) -> Result<Profiles, StorageError> {
+        let mut profiles = Profiles::open(self.data_dir(), self.save_file())?;
+        if let Some(wanted) = &self.profile {
+            let id = profiles
+                .find(wanted)
+                .map(|profile| profile.id.clone())
+                .ok_or_else(|| StorageError::UnknownProfile(wanted.clone()))?;
+            profiles.select(&id)?;
+        }
+        Ok(profiles)
+    }
+
+    /// File name each profile keeps its progress in.
+    pub fn save_file(&self) -> &'static str {
+        match env::var(BACKEND_ENV).as_deref() {
+            Ok("sqlite") => "save.db",
+            _ => "save.json",
+        }
+    }
+}
+
+/// `~/.memorykata` when an earlier version already created it; otherwise
+/// `$XDG_DATA_HOME/memorykata` (usually `~/.local/share/memorykata`) on
+/// Linux and `~/.memorykata` elsewhere.
+pub fn default_data_dir() -> PathBuf {
+    let legacy = dirs::home_dir().map(|home| home.join(".memorykata"));
+    if let Some(legacy) = legacy.clone().filter(|dir| dir.is_dir()) {
+        return legacy;
+    }
+    platform_data_dir()
+        .or(legacy)
+        .unwrap_or_else(|| PathBuf::from(".memorykata"))
+}
+
+#[cfg(target_os = "linux")]
+fn platform_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("memorykata"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn platform_data_dir() -> Option<PathBuf> {
+    None
+}
@@ -0,0 +1,214 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::games::{self, Answer, GameModule, Outcome, Question, Verdict};
+use crate::scheduler::{Clock, Scheduler};
+
+/// Rounds in every daily challenge.
+pub const DAILY_ROUNDS: usize = 10;
+/// Games the challenge draws its rounds from. The Π Challenge is left out as
+/// its rounds never change.
+const DAILY_GAMES: [&str; 7] = [
+    "greek_alphabet",
+    "music_tempo",
+    "math_tricks",
+    "physics_formulas",
+    "periodic_table",
+    "crypto_puzzle",
+    "pattern_memory",
+];
+const DAY: u64 = 24 * 60 * 60;
+
+/// Days since the Unix epoch (UTC) at the timestamp `now`.
+pub fn day_of(now: u64) -> u64 {
+    now / DAY
+}
+
+/// Seed shared by everyone playing on `day`.
+pub fn seed_for(day: u64) -> u64 {
+    // Spread consecutive days over the whole seed space.
+    (day ^ 0x6d65_6d6f_7279_6b61).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+}
+
+/// `day` as an ISO date such as `2024-03-14`.
+pub fn date(day: u64) -> String {
+    // Howard Hinnant's days-to-civil conversion.
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Outcome of one day's challenge, saved after every answer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub day: u64,
+    pub answered: usize,
+    pub correct: usize,
+    pub score: i32,
+}
+
+impl DailyResult {
+    pub fn is_complete(&self) -> bool {
+        self.answered >= DAILY_ROUNDS
+    }
+}
+
+/// Daily challenge results and streak, kept in [`SaveData`](crate::storage::SaveData).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyProgress {
+    /// One entry per day played, oldest first.
+    #[serde(default)]
+    pub results: Vec<DailyResult>,
+    /// Days in a row completed, as of the last completed day.
+    #[serde(default)]
+    pub streak: u32,
+    #[serde(default)]
+    pub best_streak: u32,
+}
+
+impl DailyProgress {
+    pub fn result(&self, day: u64) -> Option<&DailyResult> {
+        self.results.iter().rev().find(|result| result.day == day)
+    }
+
+    /// Streak still alive on `today`: the last completed day was today or yesterday.
+    pub fn current_streak(&self, today: u64) -> u32 {
+        match self.last_completed() {
+            Some(day) if day + 1 >= today => self.streak,
+            _ => 0,
+        }
+    }
+
+    fn last_completed(&self) -> Option<u64> {
+        self.results
+            .iter()
+            .rev()
+            .find(|result| result.is_complete())
+            .map(|result| result.day)
+    }
+
+    /// Stores `result`, replacing an earlier save of the same day, and
+    /// extends the streak once the day is complete.
+    fn record(&mut self, result: &DailyResult) {
+        let previous = self.last_completed();
+        match self.results.last_mut() {
+            Some(last) if last.day == result.day => *last = result.clone(),
+            _ => self.results.push(result.clone()),
+        }
+        if result.is_complete() && previous != Some(result.day) {
+            self.streak = match previous {
+                Some(day) if day + 1 == result.day => self.streak + 1,
+                _ => 1,
+            };
+            self.best_streak = self.best_streak.max(self.streak);
+        }
+    }
+}
+
+/// One day's run: a fixed sequence of rounds from several games, the same
+/// for everyone on that day whatever they answer.
+pub struct DailyChallenge {
+    modules: Vec<Box<dyn GameModule + Send + Sync>>,
+    /// Index into `modules` for every round.
+    order: Vec<usize>,
+    result: DailyResult,
+}
+
+impl DailyChallenge {
+    /// Builds the challenge for `day`, continuing after the rounds already
+    /// answered in `progress`.
+    pub fn new(day: u64, progress: &DailyProgress) -> Self {
+        let mut rng = games::seeded_rng(seed_for(day));
+        let mut modules: Vec<_> = games::all()
+            .into_iter()
+            .filter(|module| DAILY_GAMES.contains(&module.id()))
+            .collect();
+        for module in modules.iter_mut() {
+            module.set_clock(Clock::Fixed(day * DAY));
+            module.reseed(rng.gen());
+        }
+        let order = (0..DAILY_ROUNDS)
+            .map(|_| rng.gen_range(0..modules.len()))
+            .collect();
+
+        let mut challenge = Self {
+            modules,
+            order,
+            result: DailyResult {
+                day,
+                ..Default::default()
+            },
+        };
+        if let Some(saved) = progress.result(day) {
+            for _ in 0..saved.answered.min(DAILY_ROUNDS) {
+                challenge.advance();
+            }
+            challenge.result = saved.clone();
+        }
+        challenge
+    }
+
+    pub fn result(&self) -> &DailyResult {
+        &self.result
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.result.is_complete()
+    }
+
+    /// The game asking the current round.
+    pub fn game(&mut self) -> &mut dyn GameModule {
+        let index = self.order[self.result.answered.min(DAILY_ROUNDS - 1)];
+        self.modules[index].as_mut()
+    }
+
+    pub fn question(&mut self) -> &Question {
+        self.game().question()
+    }
+
+    /// The game that asked the round just answered, still showing that round
+    /// until [`DailyChallenge::next_round`].
+    pub fn answered_game(&mut self) -> Option<&mut dyn GameModule> {
+        let index = *self.order.get(self.result.answered.checked_sub(1)?)?;
+        Some(self.modules[index].as_mut())
+    }
+
+    /// Grades the current round and saves the running result into `progress`.
+    pub fn check_answer(&mut self, answer: &Answer, progress: &mut DailyProgress) -> Outcome {
+        let outcome = self.game().check_answer(answer);
+        self.result.answered += 1;
+        self.result.score += outcome.points;
+        if outcome.verdict == Verdict::Correct {
+            self.result.correct += 1;
+        }
+        progress.record(&self.result);
+        outcome
+    }
+
+    /// Moves the game that asked the last round on to its next round.
+    pub fn next_round(&mut self) {
+        if let Some(&index) = self.order.get(self.result.answered.saturating_sub(1)) {
+            Self::next_question(self.modules[index].as_mut());
+        }
+    }
+
+    /// Skips one round without answering it, used when resuming.
+    fn advance(&mut self) {
+        let index = self.order[self.result.answered];
+        Self::next_question(self.modules[index].as_mut());
+        self.result.answered += 1;
+    }
+
+    fn next_question(module: &mut (dyn GameModule + Send + Sync)) {
+        // Forget answers so that the rounds depend on the seed alone.
+        module.state_mut().schedule = Scheduler::default();
+        module.next_round();
+    }
+}
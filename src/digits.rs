@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// Digits are worked out in whole blocks of this size. Every computation
+/// starts again from the first digit, so each one also at least doubles
+/// what is already known, keeping the total work close to that of the last.
+pub const BLOCK: usize = 1000;
+/// Extra digits carried through the arithmetic to absorb rounding.
+const GUARD: usize = 10;
+
+static SHARED: OnceLock<DigitCache> = OnceLock::new();
+
+/// Keeps computed digits under `dir` from now on, so later runs start from
+/// them. Only a call made before any digits are asked for has an effect.
+pub fn set_cache_dir(dir: PathBuf) {
+    let _ = SHARED.set(DigitCache::new(Some(dir)));
+}
+
+fn shared() -> &'static DigitCache {
+    SHARED.get_or_init(|| DigitCache::new(None))
+}
+
+/// Digits generated so far for every constant, saved as one file per
+/// constant when there is a directory to keep them in.
+#[derive(Debug, Default)]
+pub struct DigitCache {
+    dir: Option<PathBuf>,
+    known: Mutex<BTreeMap<Constant, String>>,
+}
+
+impl DigitCache {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        DigitCache {
+            dir,
+            known: Mutex::default(),
+        }
+    }
+
+    /// The first `count` digits of `constant`, as [`Constant::digits`].
+    ///
+    /// Digits come from memory, then from the cache file, and are otherwise
+    /// computed and saved for next time. Nothing is locked while computing,
+    /// so other constants stay available meanwhile.
+    pub fn digits(&self, constant: Constant, count: usize) -> String {
+        let known = match self.lookup(constant, count) {
+            Ok(digits) => return digits,
+            Err(known) => known,
+        };
+        let cached = self.read_file(constant).unwrap_or_default();
+        let digits = if cached.len() >= count {
+            cached
+        } else {
+            let target = (count.div_ceil(BLOCK) * BLOCK).max(2 * known.max(cached.len()));
+            let computed = constant.compute(target);
+            self.write_file(constant, &computed);
+            computed
+        };
+        let prefix = digits[..count].to_string();
+        let mut known = self.lock();
+        let entry = known.entry(constant).or_default();
+        if digits.len() > entry.len() {
+            *entry = digits;
+        }
+        prefix
+    }
+
+    /// The digits when enough are in memory, otherwise how many are.
+    fn lookup(&self, constant: Constant, count: usize) -> Result<String, usize> {
+        match self.lock().get(&constant) {
+            Some(digits) if digits.len() >= count => Ok(digits[..count].to_string()),
+            digits => Err(digits.map_or(0, String::len)),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<Constant, String>> {
+        self.known
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn file(&self, constant: Constant) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.txt", constant.id())))
+    }
+
+    /// Reads the cache file, ignoring it when it is damaged.
+    fn read_file(&self, constant: Constant) -> Option<String> {
+        let digits = fs::read_to_string(self.file(constant)?).ok()?;
+        let digits = digits.trim();
+        (digits.bytes().all(|b| b.is_ascii_digit()) && constant.verify(digits))
+            .then(|| digits.to_string())
+    }
+
+    /// Best effort: without a cache the digits are simply computed again.
+    fn write_file(&self, constant: Constant, digits: &str) {
+        if let Some(path) = self.file(constant) {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let _ = fs::write(path, digits);
+        }
+    }
+}
+
+/// A mathematical constant whose digits can be generated on demand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Constant {
+    Pi,
+    E,
+    Phi,
+    Sqrt2,
+    Ln2,
+}
+
+impl Constant {
+    pub const ALL: [Constant; 5] = [
+        Constant::Pi,
+        Constant::E,
+        Constant::Phi,
+        Constant::Sqrt2,
+        Constant::Ln2,
+    ];
+
+    /// Stable identifier used in cache file names and saved progress.
+    pub fn id(self) -> &'static str {
+        match self {
+            Constant::Pi => "pi",
+            Constant::E => "e",
+            Constant::Phi => "phi",
+            Constant::Sqrt2 => "sqrt2",
+            Constant::Ln2 => "ln2",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Constant::Pi => "π",
+            Constant::E => "e",
+            Constant::Phi => "φ",
+            Constant::Sqrt2 => "√2",
+            Constant::Ln2 => "ln 2",
+        }
+    }
+
+    /// The first `count` significant digits, starting with the first
+    /// non-zero one (`3141…` for π, `6931…` for ln 2).
+    pub fn digits(self, count: usize) -> String {
+        shared().digits(self, count)
+    }
+
+    /// Whether `digits` agrees with every reference position it reaches.
+    pub fn verify(self, digits: &str) -> bool {
+        self.references().iter().all(|&(start, expected)| {
+            let end = (start + expected.len()).min(digits.len());
+            start >= end || digits[start..end] == expected[..end - start]
+        })
+    }
+
+    /// Published digits every computed or cached sequence is checked against.
+    fn references(self) -> &'static [(usize, &'static str)] {
+        match self {
+            // The opening digits and the Feynman point, six nines from the 762nd decimal.
+            Constant::Pi => &[
+                (0, "31415926535897932384626433832795028841971693993751"),
+                (50, "05820974944592307816406286208998628034825342117067"),
+                (762, "999999"),
+            ],
+            Constant::E => &[(0, "27182818284590452353602874713526624977572470936999")],
+            Constant::Phi => &[(0, "16180339887498948482045868343656381177203091798057")],
+            Constant::Sqrt2 => &[(0, "14142135623730950488016887242096980785696718753769")],
+            Constant::Ln2 => &[(0, "69314718055994530941723212145817656807550013436025")],
+        }
+    }
+
+    /// Works out `count` digits in fixed point.
+    fn compute(self, count: usize) -> String {
+        // Every constant but ln 2 has one digit before the decimal point.
+        let decimals = match self {
+            Constant::Ln2 => count + GUARD,
+            _ => count - 1 + GUARD,
+        };
+        let scale = BigUint::from(10u32).pow(decimals as u32);
+        let value = match self {
+            // Machin: π = 16·arctan(1/5) − 4·arctan(1/239)
+            Constant::Pi => arctan_inverse(5, &scale) * 16u32 - arctan_inverse(239, &scale) * 4u32,
+            // e = Σ 1/k!
+            Constant::E => {
+                let mut term = scale.clone();
+                let mut sum = scale.clone();
+                let mut k = 1u32;
+                while term != BigUint::default() {
+                    term /= k;
+                    sum += &term;
+                    k += 1;
+                }
+                sum
+            }
+            // φ = (1 + √5) / 2
+            Constant::Phi => (&scale + (&scale * &scale * 5u32).sqrt()) / 2u32,
+            Constant::Sqrt2 => (&scale * &scale * 2u32).sqrt(),
+            // ln 2 = 2·artanh(1/3)
+            Constant::Ln2 => artanh_inverse(3, &scale) * 2u32,
+        };
+        let mut digits = value.to_string();
+        digits.truncate(count);
+        debug_assert!(
+            self.verify(&digits),
+            "computed digits of {} are wrong",
+            self.symbol()
+        );
+        digits
+    }
+}
+
+/// arctan(1/x)·scale, summing the Taylor series until its terms vanish.
+fn arctan_inverse(x: u32, scale: &BigUint) -> BigUint {
+    let (positive, negative) = odd_power_series(x, scale);
+    positive - negative
+}
+
+/// artanh(1/x)·scale: the arctangent series without the alternating signs.
+fn artanh_inverse(x: u32, scale: &BigUint) -> BigUint {
+    let (positive, negative) = odd_power_series(x, scale);
+    positive + negative
+}
+
+/// Sums of the terms 1/((2k+1)·x^(2k+1)) for even and for odd k.
+fn odd_power_series(x: u32, scale: &BigUint) -> (BigUint, BigUint) {
+    let x_squared = BigUint::from(x) * x;
+    let mut power = scale / x;
+    let mut even = power.clone();
+    let mut odd = BigUint::default();
+    let mut divisor = 1u32;
+    loop {
+        power /= &x_squared;
+        divisor += 2;
+        let term = &power / divisor;
+        if term == BigUint::default() {
+            break;
+        }
+        if divisor % 4 == 3 {
+            odd += term;
+        } else {
+            even += term;
+        }
+    }
+    (even, odd)
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::digits::Constant;
+
+use super::major_system::{self, Pegs};
+use super::recall::{Recall, Sequence};
+use super::{Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question};
+
+/// Entry holding the id of the sequence being practised.
+const SELECTED: &str = "selected";
+/// Prefix of the entries holding player-supplied sequences, by name.
+const CUSTOM: &str = "custom.";
+/// Constants offered besides the player's own sequences; π has its own kata.
+const CONSTANTS: [Constant; 4] = [Constant::E, Constant::Phi, Constant::Sqrt2, Constant::Ln2];
+
+/// The Π Challenge recall loop for other constants and for digit strings the
+/// player adds, such as phone or card numbers. Every sequence keeps its own
+/// frontier and best streak.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConstantChallenge {
+    recall: Recall,
+    state: GameState,
+    new_name: String,
+    new_digits: String,
+    status: Option<String>,
+}
+
+impl ConstantChallenge {
+    pub fn new() -> Self {
+        let mut module = Self {
+            recall: Recall::new(Sequence::Constant(CONSTANTS[0]), true),
+            state: GameState {
+                score: 0,
+                attempts: 0,
+                description: "Recall e, φ, √2, ln 2 or your own numbers".into(),
+                ..Default::default()
+            },
+            new_name: String::new(),
+            new_digits: String::new(),
+            status: None,
+        };
+        module.next_round();
+        module
+    }
+
+    /// The built-in constants followed by the player's sequences.
+    pub fn sequences(&self) -> Vec<Sequence> {
+        let custom = self.state.entries.iter().filter_map(|(key, digits)| {
+            key.strip_prefix(CUSTOM).map(|name| Sequence::Custom {
+                name: name.to_string(),
+                digits: digits.clone(),
+            })
+        });
+        CONSTANTS
+            .into_iter()
+            .map(Sequence::Constant)
+            .chain(custom)
+            .collect()
+    }
+
+    pub fn selected(&self) -> &Sequence {
+        self.recall.sequence()
+    }
+
+    /// Switches to the sequence with [`Sequence::id`] `id`; false if there is none.
+    pub fn select(&mut self, id: &str) -> bool {
+        if !self.sequences().iter().any(|sequence| sequence.id() == id) {
+            return false;
+        }
+        self.state.entries.insert(SELECTED.into(), id.to_string());
+        self.next_round();
+        true
+    }
+
+    /// Adds a sequence of the player's own and switches to it. Spaces,
+    /// dashes, dots and slashes in `digits` are ignored.
+    pub fn add_custom(&mut self, name: &str, digits: &str) -> Result<(), String> {
+        let name = name.trim();
+        let digits: String = digits
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '.' | '/'))
+            .collect();
+        if name.is_empty() {
+            return Err("give the sequence a name".into());
+        }
+        let key = format!("{}{}", CUSTOM, name);
+        if self.state.entries.contains_key(&key)
+            || CONSTANTS.iter().any(|constant| constant.symbol() == name)
+        {
+            return Err(format!("there is already a sequence called '{}'", name));
+        }
+        if digits.len() < 2 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("a sequence needs at least two digits and nothing else".into());
+        }
+        self.state.entries.insert(key.clone(), digits);
+        self.select(&key);
+        Ok(())
+    }
+
+    /// Deletes a player sequence and its progress.
+    pub fn remove_custom(&mut self, name: &str) {
+        let sequence = self
+            .sequences()
+            .into_iter()
+            .find(|sequence| matches!(sequence, Sequence::Custom { name: n, .. } if n == name));
+        if let Some(sequence) = sequence {
+            let id = sequence.id();
+            Recall::new(sequence, true).forget(&mut self.state);
+            self.state.entries.remove(&id);
+            if self.state.entries.get(SELECTED) == Some(&id) {
+                self.state.entries.remove(SELECTED);
+            }
+            self.next_round();
+        }
+    }
+}
+
+impl Default for ConstantChallenge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameEngine for ConstantChallenge {
+    fn id(&self) -> &'static str {
+        "constant_challenge"
+    }
+
+    fn name(&self) -> &str {
+        "Constant Challenge"
+    }
+
+    fn description(&self) -> &str {
+        &self.state.description
+    }
+
+    fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut GameState {
+        &mut self.state
+    }
+
+    /// Forgets the progress on every sequence but keeps the player's own.
+    fn reset(&mut self) {
+        self.state.attempts = 0;
+        self.state.score = 0;
+        for sequence in self.sequences() {
+            Recall::new(sequence, true).forget(&mut self.state);
+        }
+        self.next_round();
+    }
+
+    /// Follows the saved selection, falling back to e when it is gone.
+    fn next_round(&mut self) {
+        let selected = self
+            .state
+            .entries
+            .get(SELECTED)
+            .cloned()
+            .unwrap_or_default();
+        let sequence = self
+            .sequences()
+            .into_iter()
+            .find(|sequence| sequence.id() == selected)
+            .unwrap_or(Sequence::Constant(CONSTANTS[0]));
+        if *self.recall.sequence() != sequence {
+            self.recall.switch_to(sequence);
+        }
+        self.recall.next_round(&self.state);
+    }
+
+    fn set_rng(&mut self, rng: GameRng) {
+        self.recall.set_rng(rng);
+        self.next_round();
+    }
+
+    fn share_progress(&mut self, games: &BTreeMap<String, GameState>) {
+        let pegs = games.get(major_system::ID).map(Pegs::of).unwrap_or_default();
+        self.recall.set_pegs(pegs);
+    }
+
+    fn question(&self) -> &Question {
+        self.recall.question()
+    }
+
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
+        self.recall.check_answer(&mut self.state, answer)
+    }
+}
+
+impl GameModule for ConstantChallenge {
+    fn study_ended(&self) -> Option<Instant> {
+        self.recall.study_ended()
+    }
+
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
+        ui.heading("Constant Challenge");
+
+        let mut chosen = None;
+        let mut removed = None;
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Sequence")
+                .selected_text(self.selected().label().to_string())
+                .show_ui(ui, |ui| {
+                    for sequence in self.sequences() {
+                        let current = sequence == *self.selected();
+                        if ui.selectable_label(current, sequence.label()).clicked() {
+                            chosen = Some(sequence.id());
+                        }
+                    }
+                });
+            if let Sequence::Custom { name, .. } = self.selected() {
+                if ui.small_button("Remove").clicked() {
+                    removed = Some(name.clone());
+                }
+            }
+        });
+        if let Some(id) = chosen {
+            self.select(&id);
+        }
+        if let Some(name) = removed {
+            self.remove_custom(&name);
+        }
+
+        let submitted = self.recall.update(ui, &mut self.state);
+
+        ui.collapsing("Add your own number", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Name");
+                ui.text_edit_singleline(&mut self.new_name);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Digits");
+                ui.text_edit_singleline(&mut self.new_digits);
+            });
+            if ui.button("Add").clicked() {
+                let (name, digits) = (self.new_name.clone(), self.new_digits.clone());
+                self.status = Some(match self.add_custom(&name, &digits) {
+                    Ok(()) => {
+                        self.new_name.clear();
+                        self.new_digits.clear();
+                        format!("✓ Added {}", name.trim())
+                    }
+                    Err(err) => format!("⚠ {}", err),
+                });
+            }
+            if let Some(status) = &self.status {
+                ui.label(status);
+            }
+        });
+
+        ui.label(format!("Score: {}", self.state.score));
+        ui.label(format!("Attempts: {}", self.state.attempts));
+        submitted
+    }
+}
@@ -1,9 +1,16 @@
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
-use rand::{seq::SliceRandom, Rng};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use super::{GameModule, GameState};
+use crate::scheduler::Clock;
+
+use super::{
+    Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question, QuestionKind,
+    Verdict,
+};
+
+const WORDS: &[&str] = &["HELLO", "MEMORY", "RUST", "KATA", "BRAIN"];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum CipherKind {
@@ -15,51 +22,83 @@ pub enum CipherKind {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CryptoPuzzle {
     cipher: CipherKind,
-    plaintext: String,
-    ciphertext: String,
+    question: Question,
     answer: String,
     state: GameState,
+    #[serde(skip, default = "super::fresh_rng")]
+    rng: GameRng,
+    #[serde(skip)]
+    clock: Clock,
 }
 
 impl CryptoPuzzle {
     pub fn new() -> Self {
         let mut module = Self {
             cipher: CipherKind::Caesar,
-            plaintext: String::new(),
-            ciphertext: String::new(),
+            question: Question::default(),
             answer: String::new(),
             state: GameState {
                 score: 0,
                 attempts: 0,
                 description: "Decode simple ciphers".into(),
+                ..Default::default()
             },
+            rng: super::fresh_rng(),
+            clock: Clock::System,
         };
         module.reset();
         module
     }
 
     fn generate_puzzle(&mut self) {
-        let mut rng = rand::thread_rng();
-        let words = vec!["HELLO", "MEMORY", "RUST", "KATA", "BRAIN"];
-        self.plaintext = words.choose(&mut rng).unwrap_or(&"RUST").to_string();
-        self.cipher = match rng.gen_range(0..3) {
+        let next = self
+            .state
+            .schedule
+            .pick(WORDS.iter().copied(), self.clock.now(), &mut self.rng);
+        let plaintext = next
+            .and_then(|index| WORDS.get(index))
+            .unwrap_or(&"RUST")
+            .to_string();
+        self.cipher = match self.rng.gen_range(0..3) {
             0 => CipherKind::Caesar,
             1 => CipherKind::XOR,
             _ => CipherKind::Base64,
         };
 
-        self.ciphertext = match self.cipher {
-            CipherKind::Caesar => self.plaintext
+        let ciphertext: String = match self.cipher {
+            CipherKind::Caesar => plaintext
                 .chars()
                 .map(|c| (((c as u8 - b'A' + 3) % 26) + b'A') as char)
                 .collect(),
-            CipherKind::XOR => self
-                .plaintext
+            CipherKind::XOR => plaintext
                 .bytes()
                 .map(|b| (b ^ 0x2A) as char)
                 .collect(),
-            CipherKind::Base64 => STANDARD.encode(&self.plaintext),
+            CipherKind::Base64 => STANDARD.encode(&plaintext),
         };
+
+        let hint = format!("{} letters", plaintext.len());
+        let explanation = match self.cipher {
+            CipherKind::Caesar => "Shift every letter back by three places",
+            CipherKind::XOR => "XOR every byte with 0x2A again to undo the cipher",
+            CipherKind::Base64 => "Decode the Base64 text back into ASCII",
+        };
+        self.question = Question::new(
+            format!("Cipher: {}\nCiphertext: {}", self.cipher_name(), ciphertext),
+            QuestionKind::Text,
+            vec![plaintext.clone()],
+        )
+        .with_item(plaintext)
+        .with_hint(hint)
+        .with_explanation(explanation);
+    }
+
+    fn cipher_name(&self) -> &'static str {
+        match self.cipher {
+            CipherKind::Caesar => "Caesar shift +3",
+            CipherKind::XOR => "XOR key 0x2A",
+            CipherKind::Base64 => "Base64 ✱",
+        }
     }
 }
 
@@ -69,7 +108,11 @@ impl Default for CryptoPuzzle {
     }
 }
 
-impl GameModule for CryptoPuzzle {
+impl GameEngine for CryptoPuzzle {
+    fn id(&self) -> &'static str {
+        "crypto_puzzle"
+    }
+
     fn name(&self) -> &str {
         "Crypto Puzzle"
     }
@@ -87,36 +130,52 @@ impl GameModule for CryptoPuzzle {
     }
 
     fn reset(&mut self) {
+        self.next_round();
+    }
+
+    fn next_round(&mut self) {
         self.generate_puzzle();
         self.answer.clear();
     }
 
-    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) {
-        ui.heading("Crypto Puzzle");
-        let cipher_name = match self.cipher {
-            CipherKind::Caesar => "Caesar shift +3",
-            CipherKind::XOR => "XOR key 0x2A",
-            CipherKind::Base64 => "Base64 ✱",
-        };
-        ui.label(format!("Cipher: {}", cipher_name));
-        ui.label(format!("Ciphertext: {}", self.ciphertext));
-        ui.add(egui::TextEdit::singleline(&mut self.answer));
+    fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+        self.next_round();
+    }
 
-        if ui.button("Decode").clicked() {
-            self.check_answer();
-        }
+    fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
 
-        ui.label(format!("Score: {}", self.state.score));
-        ui.label(format!("Attempts: {}", self.state.attempts));
+    fn question(&self) -> &Question {
+        &self.question
     }
 
-    fn check_answer(&mut self) {
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
         self.state.attempts += 1;
-        if self.answer.trim().eq_ignore_ascii_case(&self.plaintext) {
-            self.state.score += 4;
+        let outcome = if self.question.accepts(&answer.text) {
+            Outcome::graded(Verdict::Correct, 4, &self.question, answer)
         } else {
-            self.state.score -= 1;
-        }
-        self.reset();
+            Outcome::graded(Verdict::Wrong, -1, &self.question, answer)
+        };
+        self.state.score += outcome.points;
+        self.state
+            .schedule
+            .record(&self.question, &outcome, self.clock.now());
+        outcome
+    }
+}
+
+impl GameModule for CryptoPuzzle {
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
+        ui.heading("Crypto Puzzle");
+        ui.label(&self.question.prompt);
+        ui.add(egui::TextEdit::singleline(&mut self.answer));
+
+        let submitted = ui.button("Decode").clicked();
+
+        ui.label(format!("Score: {}", self.state.score));
+        ui.label(format!("Attempts: {}", self.state.attempts));
+        submitted.then(|| std::mem::take(&mut self.answer))
     }
 }
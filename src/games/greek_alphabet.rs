@@ -1,42 +1,337 @@
-use rand::seq::SliceRandom;
-use rand::thread_rng;
 use serde::{Deserialize, Serialize};
 
-use super::{GameModule, GameState};
-
-const GREEK_LETTERS: &[(&str, &str)] = &[
-    ("Α", "Alpha"),
-    ("Β", "Beta"),
-    ("Γ", "Gamma"),
-    ("Δ", "Delta"),
-    ("Ω", "Omega"),
-    ("μ", "Mu"),
-    ("π", "Pi"),
-    ("λ", "Lambda"),
+use crate::scheduler::Clock;
+
+use super::{
+    Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question, QuestionKind,
+    Verdict,
+};
+
+/// Every letter as capital, small, name and Latin transliteration.
+const GREEK_LETTERS: &[(&str, &str, &str, &str)] = &[
+    ("Α", "α", "Alpha", "a"),
+    ("Β", "β", "Beta", "b"),
+    ("Γ", "γ", "Gamma", "g"),
+    ("Δ", "δ", "Delta", "d"),
+    ("Ε", "ε", "Epsilon", "e"),
+    ("Ζ", "ζ", "Zeta", "z"),
+    ("Η", "η", "Eta", "e"),
+    ("Θ", "θ", "Theta", "th"),
+    ("Ι", "ι", "Iota", "i"),
+    ("Κ", "κ", "Kappa", "k"),
+    ("Λ", "λ", "Lambda", "l"),
+    ("Μ", "μ", "Mu", "m"),
+    ("Ν", "ν", "Nu", "n"),
+    ("Ξ", "ξ", "Xi", "x"),
+    ("Ο", "ο", "Omicron", "o"),
+    ("Π", "π", "Pi", "p"),
+    ("Ρ", "ρ", "Rho", "r"),
+    ("Σ", "σ", "Sigma", "s"),
+    ("Τ", "τ", "Tau", "t"),
+    ("Υ", "υ", "Upsilon", "y"),
+    ("Φ", "φ", "Phi", "ph"),
+    ("Χ", "χ", "Chi", "ch"),
+    ("Ψ", "ψ", "Psi", "ps"),
+    ("Ω", "ω", "Omega", "o"),
+];
+
+/// Other forms of small letters: the form, its usual small letter and what it is.
+const VARIANTS: &[(&str, &str, &str)] = &[
+    ("ς", "σ", "final sigma, written at the end of a word"),
+    ("ϑ", "θ", "a variant of theta"),
+    ("ϕ", "φ", "a variant of phi"),
+    ("ϵ", "ε", "a variant of epsilon"),
+];
+
+/// Accented and diaeresis vowels, by the letter they mark.
+const MARKED: &[(char, char)] = &[
+    ('ά', 'α'),
+    ('έ', 'ε'),
+    ('ή', 'η'),
+    ('ί', 'ι'),
+    ('ϊ', 'ι'),
+    ('ΐ', 'ι'),
+    ('ό', 'ο'),
+    ('ύ', 'υ'),
+    ('ϋ', 'υ'),
+    ('ΰ', 'υ'),
+    ('ώ', 'ω'),
+];
+
+/// Short words for transliteration, most of them familiar from English.
+const WORDS: &[&str] = &[
+    "λόγος",
+    "φίλος",
+    "θεός",
+    "ψυχή",
+    "κόσμος",
+    "σοφία",
+    "μύθος",
+    "χάος",
+    "δράμα",
+    "ζώνη",
+    "ξένος",
+    "πόλις",
+    "τέχνη",
+    "γένεσις",
+    "κύκλος",
+    "νέκταρ",
+    "ωκεανός",
+    "αυτός",
 ];
 
+/// The Latin spelling of a Greek word, letter by letter: `λόγος` is `logos`.
+/// Upsilon is `y` on its own and `u` after a vowel, as in `autos`.
+pub fn transliterate(word: &str) -> String {
+    let mut latin = String::new();
+    let mut previous = None;
+    for letter in word.chars().flat_map(char::to_lowercase) {
+        let letter = MARKED
+            .iter()
+            .find(|&&(marked, _)| marked == letter)
+            .map_or(letter, |&(_, plain)| plain);
+        let mut buffer = [0; 4];
+        let small: &str = letter.encode_utf8(&mut buffer);
+        let small = VARIANTS
+            .iter()
+            .find(|&&(variant, _, _)| variant == small)
+            .map_or(small, |&(_, plain, _)| plain);
+        match GREEK_LETTERS.iter().find(|&&(_, s, _, _)| s == small) {
+            Some(_) if small == "υ" && matches!(previous, Some('α' | 'ε' | 'η' | 'ο')) => {
+                latin.push('u')
+            }
+            Some(&(_, _, _, roman)) => latin.push_str(roman),
+            None => latin.push(letter),
+        }
+        previous = Some(letter);
+    }
+    latin
+}
+
+/// Spellings graded as correct for `word`: the usual one, and `u` for `y`
+/// and `kh` for `ch` as other schemes write them.
+fn transliterations(word: &str) -> Vec<String> {
+    let usual = transliterate(word);
+    let other = usual.replace('y', "u").replace("ch", "kh");
+    if other == usual {
+        vec![usual]
+    } else {
+        vec![usual, other]
+    }
+}
+
+/// Counter holding the [`Mode`] practised, by [`Mode::code`].
+const MODE: &str = "mode";
+
+/// What the rounds ask.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mode {
+    /// A capital, small or variant letter, answered with its name.
+    #[default]
+    Names,
+    /// A name and case, answered with the letter, typed or picked.
+    Symbols,
+    /// A letter, answered with the same letter in the other case.
+    Cases,
+    /// A short Greek word, answered in Latin letters.
+    Words,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 4] = [Mode::Names, Mode::Symbols, Mode::Cases, Mode::Words];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Mode::Names => "Symbol → name",
+            Mode::Symbols => "Name → symbol",
+            Mode::Cases => "Capital ↔ small",
+            Mode::Words => "Transliterate words",
+        }
+    }
+
+    /// Stored in the counters of the game.
+    pub fn code(self) -> u32 {
+        match self {
+            Mode::Names => 0,
+            Mode::Symbols => 1,
+            Mode::Cases => 2,
+            Mode::Words => 3,
+        }
+    }
+
+    fn from_code(code: u32) -> Self {
+        Mode::ALL
+            .into_iter()
+            .find(|mode| mode.code() == code)
+            .unwrap_or_default()
+    }
+}
+
+/// A small letter and the variant forms also accepted for it.
+fn small_forms(small: &str) -> Vec<String> {
+    let variants = VARIANTS
+        .iter()
+        .filter(|&&(_, plain, _)| plain == small)
+        .map(|&(variant, _, _)| variant.to_string());
+    std::iter::once(small.to_string()).chain(variants).collect()
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GreekAlphabet {
-    #[serde(skip, default)]
-    prompt: Option<(&'static str, &'static str)>,
+    question: Question,
     answer: String,
     state: GameState,
+    #[serde(skip, default = "super::fresh_rng")]
+    rng: GameRng,
+    #[serde(skip)]
+    clock: Clock,
 }
 
 impl GreekAlphabet {
     pub fn new() -> Self {
         let mut module = Self {
-            prompt: None,
+            question: Question::default(),
             answer: String::new(),
             state: GameState {
                 score: 0,
                 attempts: 0,
-                description: "Match Greek symbols to their names".into(),
+                description: "Name, write and transliterate Greek letters".into(),
+                ..Default::default()
             },
+            rng: super::fresh_rng(),
+            clock: Clock::System,
         };
         module.reset();
         module
     }
+
+    pub fn mode(&self) -> Mode {
+        Mode::from_code(self.state.counters.get(MODE).copied().unwrap_or(0))
+    }
+
+    /// Switches what the rounds ask, starting a new round.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.state.counters.insert(MODE.into(), mode.code());
+        self.next_round();
+    }
+
+    /// Picks the next item from `items`, the scheduler keys of this mode.
+    fn pick(&mut self, items: &[String]) -> usize {
+        let items = items.iter().map(String::as_str);
+        let next = self.state.schedule.pick(items, self.clock.now(), &mut self.rng);
+        next.unwrap_or(0)
+    }
+
+    /// Every capital, small and variant letter, named.
+    fn names_question(&mut self) -> Question {
+        let mut symbols: Vec<(&str, &str, String)> = Vec::new();
+        for &(capital, small, name, _) in GREEK_LETTERS {
+            symbols.push((
+                capital,
+                name,
+                format!("{} is the capital letter {}", capital, name),
+            ));
+            symbols.push((
+                small,
+                name,
+                format!("{} is the small letter {}", small, name),
+            ));
+        }
+        for &(variant, plain, what) in VARIANTS {
+            let name = GREEK_LETTERS
+                .iter()
+                .find(|&&(_, small, _, _)| small == plain)
+                .map_or("", |&(_, _, name, _)| name);
+            symbols.push((variant, name, format!("{} is {}", variant, what)));
+        }
+        let items: Vec<String> = symbols
+            .iter()
+            .map(|(symbol, _, _)| symbol.to_string())
+            .collect();
+        let (symbol, name, explanation) = symbols.swap_remove(self.pick(&items));
+        Question::new(
+            format!("Symbol: {}", symbol),
+            QuestionKind::Text,
+            vec![name.into()],
+        )
+        .with_item(symbol)
+        .with_hint(format!("Starts with “{}”", &name[..1]))
+        .with_explanation(explanation)
+    }
+
+    /// A name in either case, answered with the letter.
+    fn symbols_question(&mut self) -> Question {
+        let items: Vec<String> = GREEK_LETTERS
+            .iter()
+            .flat_map(|&(capital, small, _, _)| [capital, small])
+            .map(|symbol| format!("name:{}", symbol))
+            .collect();
+        let index = self.pick(&items);
+        let (capital, small, name, roman) = GREEK_LETTERS[index / 2];
+        let (prompt, accepted) = if index.is_multiple_of(2) {
+            (format!("Capital {}", name), vec![capital.to_string()])
+        } else {
+            (format!("Small {}", name.to_lowercase()), small_forms(small))
+        };
+        Question::new(prompt, QuestionKind::Text, accepted)
+            .with_item(items[index].clone())
+            .with_hint(format!("Written “{}” in Latin letters", roman))
+            .with_explanation(format!("{} {} is {}", capital, small, name))
+    }
+
+    /// A letter, answered in the other case. Variants are asked for their capital.
+    fn cases_question(&mut self) -> Question {
+        let mut pairs: Vec<(String, Vec<String>, &str)> = Vec::new();
+        for &(capital, small, name, _) in GREEK_LETTERS {
+            pairs.push((capital.into(), small_forms(small), name));
+            pairs.push((small.into(), vec![capital.into()], name));
+        }
+        for &(variant, plain, _) in VARIANTS {
+            let (capital, name) = GREEK_LETTERS
+                .iter()
+                .find(|&&(_, small, _, _)| small == plain)
+                .map_or(("", ""), |&(capital, _, name, _)| (capital, name));
+            pairs.push((variant.into(), vec![capital.into()], name));
+        }
+        let items: Vec<String> = pairs
+            .iter()
+            .map(|(shown, _, _)| format!("case:{}", shown))
+            .collect();
+        let index = self.pick(&items);
+        let (shown, accepted, name) = pairs.swap_remove(index);
+        let wanted = if index.is_multiple_of(2) && index < 2 * GREEK_LETTERS.len() {
+            "Small"
+        } else {
+            "Capital"
+        };
+        let explanation = format!("{} and {} are both {}", shown, accepted[0], name);
+        Question::new(
+            format!("{} form of {}", wanted, shown),
+            QuestionKind::Text,
+            accepted,
+        )
+        .with_item(items[index].clone())
+        .with_hint(name)
+        .with_explanation(explanation)
+    }
+
+    /// A short word, answered in Latin letters.
+    fn words_question(&mut self) -> Question {
+        let items: Vec<String> = WORDS.iter().map(|word| format!("word:{}", word)).collect();
+        let word = WORDS[self.pick(&items)];
+        let letters: Vec<String> = word
+            .chars()
+            .map(|letter| format!("{} {}", letter, transliterate(&letter.to_string())))
+            .collect();
+        Question::new(
+            format!("Write in Latin letters: {}", word),
+            QuestionKind::Text,
+            transliterations(word),
+        )
+        .with_item(format!("word:{}", word))
+        .with_hint("θ th · φ ph · χ ch · ψ ps · ξ x · η e · ω o · υ y")
+        .with_explanation(letters.join(" · "))
+    }
 }
 
 impl Default for GreekAlphabet {
@@ -45,7 +340,11 @@ impl Default for GreekAlphabet {
     }
 }
 
-impl GameModule for GreekAlphabet {
+impl GameEngine for GreekAlphabet {
+    fn id(&self) -> &'static str {
+        "greek_alphabet"
+    }
+
     fn name(&self) -> &str {
         "Greek Alphabet"
     }
@@ -63,37 +362,99 @@ impl GameModule for GreekAlphabet {
     }
 
     fn reset(&mut self) {
-        let mut rng = thread_rng();
-        self.prompt = GREEK_LETTERS.choose(&mut rng).copied();
+        self.next_round();
+    }
+
+    fn next_round(&mut self) {
+        self.question = match self.mode() {
+            Mode::Names => self.names_question(),
+            Mode::Symbols => self.symbols_question(),
+            Mode::Cases => self.cases_question(),
+            Mode::Words => self.words_question(),
+        };
         self.answer.clear();
     }
 
-    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) {
+    fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+        self.next_round();
+    }
+
+    fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    fn question(&self) -> &Question {
+        &self.question
+    }
+
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
+        self.state.attempts += 1;
+        let points = match self.mode() {
+            Mode::Words => 3,
+            _ => 2,
+        };
+        let outcome = if self.question.accepts(&answer.text) {
+            Outcome::graded(Verdict::Correct, points, &self.question, answer)
+        } else {
+            Outcome::graded(Verdict::Wrong, -1, &self.question, answer)
+        };
+        self.state.score += outcome.points;
+        self.state
+            .schedule
+            .record(&self.question, &outcome, self.clock.now());
+        outcome
+    }
+}
+
+impl GameModule for GreekAlphabet {
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
         ui.heading("Greek Alphabet");
-        if let Some((symbol, _)) = self.prompt {
-            ui.label(format!("Symbol: {}", symbol));
+
+        let mut mode = self.mode();
+        egui::ComboBox::from_label("Mode")
+            .selected_text(mode.label())
+            .show_ui(ui, |ui| {
+                for option in Mode::ALL {
+                    ui.selectable_value(&mut mode, option, option.label());
+                }
+            });
+        if mode != self.mode() {
+            self.set_mode(mode);
         }
 
-        ui.label("Type the matching name:");
+        ui.label(egui::RichText::new(&self.question.prompt).size(24.0));
+        ui.label(match mode {
+            Mode::Names => "Type the matching name:",
+            Mode::Symbols | Mode::Cases => "Type or pick the letter:",
+            Mode::Words => "Type the word in Latin letters:",
+        });
         ui.add(egui::TextEdit::singleline(&mut self.answer));
 
-        if ui.button("Check").clicked() {
-            self.check_answer();
+        let mut submitted = ui.button("Check").clicked();
+
+        if matches!(mode, Mode::Symbols | Mode::Cases) {
+            let capitals = GREEK_LETTERS.iter().map(|&(capital, _, _, _)| capital);
+            let smalls = GREEK_LETTERS.iter().map(|&(_, small, _, _)| small);
+            let variants = VARIANTS.iter().map(|&(variant, _, _)| variant);
+            for row in [
+                capitals.collect::<Vec<_>>(),
+                smalls.chain(variants).collect(),
+            ] {
+                ui.horizontal_wrapped(|ui| {
+                    for symbol in row {
+                        let button = egui::Button::new(egui::RichText::new(symbol).size(20.0));
+                        if ui.add(button).clicked() {
+                            self.answer = symbol.to_string();
+                            submitted = true;
+                        }
+                    }
+                });
+            }
         }
 
         ui.label(format!("Score: {}", self.state.score));
         ui.label(format!("Attempts: {}", self.state.attempts));
-    }
-
-    fn check_answer(&mut self) {
-        self.state.attempts += 1;
-        if let Some((_, name)) = self.prompt {
-            if self.answer.trim().eq_ignore_ascii_case(name) {
-                self.state.score += 2;
-            } else {
-                self.state.score -= 1;
-            }
-        }
-        self.reset();
+        submitted.then(|| std::mem::take(&mut self.answer))
     }
 }
@@ -0,0 +1,697 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::scheduler::Clock;
+
+use super::{
+    Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question, QuestionKind, Verdict,
+};
+
+/// [`GameEngine::id`] of the Major System, whose pegs other games show.
+pub const ID: &str = "major_system";
+
+/// Consonant sounds of each digit in the classic Major System, as spelled.
+/// Letters that appear nowhere (vowels, h, w, y, x) carry no digit.
+pub const DEFAULT_SOUNDS: [&str; 10] = [
+    "s z",
+    "t d",
+    "n",
+    "m",
+    "r",
+    "l",
+    "j sh ch",
+    "k g c q ck",
+    "f v",
+    "p b",
+];
+
+/// A peg word for every number from 00 to 99 under [`DEFAULT_SOUNDS`].
+pub const PEGS: [&str; 100] = [
+    "zoos", "suit", "sun", "sumo", "sore", "sail", "sushi", "sock", "sofa", "soap", //
+    "toes", "tot", "tin", "team", "tire", "tail", "dish", "duck", "dove", "tub", //
+    "nose", "net", "nun", "name", "near", "nail", "niche", "neck", "navy", "nap", //
+    "mouse", "mat", "moon", "mummy", "mower", "mail", "mash", "mug", "movie", "map", //
+    "rose", "rat", "rain", "ram", "rear", "rail", "rash", "rock", "roof", "rope", //
+    "lasso", "lid", "lion", "lime", "lair", "lily", "leash", "lake", "leaf", "lip", //
+    "cheese", "jet", "chain", "jam", "jar", "jail", "shush", "jack", "chef", "ship", //
+    "case", "cat", "coin", "game", "car", "coal", "cash", "cake", "cave", "cap", //
+    "fez", "foot", "fan", "foam", "fire", "file", "fish", "fog", "fife", "fob", //
+    "bus", "bat", "bone", "beam", "bear", "bell", "beach", "book", "beef", "pipe", //
+];
+
+/// Pegs for a digit left over at the end of an odd-length number.
+pub const DIGIT_PEGS: [&str; 10] = [
+    "zoo", "tea", "hen", "home", "ray", "law", "shoe", "cow", "ivy", "bee",
+];
+
+/// A player's peg words under their own sounds: the words they chose, or
+/// else the bundled ones, as long as they still spell their number.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pegs {
+    mapping: Mapping,
+    /// Saved peg words by number, already checked against `mapping`.
+    words: BTreeMap<usize, String>,
+}
+
+impl Pegs {
+    /// The sounds and pegs saved in the [`GameState`] of the Major System.
+    pub fn of(state: &GameState) -> Self {
+        let mapping = mapping_of(state);
+        let words = state
+            .entries
+            .iter()
+            .filter_map(|(key, word)| {
+                let number = key.strip_prefix(PEG)?;
+                if mapping.digits(word) != number {
+                    return None;
+                }
+                Some((number.parse().ok()?, word.clone()))
+            })
+            .collect();
+        Self { mapping, words }
+    }
+
+    pub fn mapping(&self) -> &Mapping {
+        &self.mapping
+    }
+
+    /// The peg word for `number` from 00 to 99, if one spells it.
+    pub fn word(&self, number: usize) -> Option<&str> {
+        self.words.get(&number).map(String::as_str).or_else(|| {
+            PEGS.get(number)
+                .copied()
+                .filter(|peg| self.spells(peg, number, 2))
+        })
+    }
+
+    fn spells(&self, word: &str, number: usize, width: usize) -> bool {
+        self.mapping.digits(word) == format!("{:0width$}", number)
+    }
+
+    /// `digits` two at a time with their peg words, and a single digit left
+    /// over at the end with one of [`DIGIT_PEGS`]. Numbers without a word
+    /// that spells them show their sounds instead, such as `31 m-t/d`.
+    fn pairs(&self, digits: &str) -> Vec<(String, String)> {
+        let digits: Vec<usize> = digits
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| d as usize)
+            .collect();
+        digits
+            .chunks(2)
+            .map(|chunk| {
+                let number = chunk.iter().fold(0, |number, digit| number * 10 + digit);
+                let word = match *chunk {
+                    [_, _] => self.word(number),
+                    _ => Some(DIGIT_PEGS[number]).filter(|peg| self.spells(peg, number, 1)),
+                };
+                let shown = chunk.iter().map(|digit| digit.to_string()).collect();
+                let sounds = chunk
+                    .iter()
+                    .map(|&digit| self.mapping.sounds(digit).join("/"))
+                    .collect::<Vec<_>>()
+                    .join("-");
+                (shown, word.map_or(sounds, String::from))
+            })
+            .collect()
+    }
+
+    /// Peg words for `digits` two at a time, such as `31 mat · 41 rat · 5 law`.
+    pub fn mnemonic(&self, digits: &str) -> String {
+        self.pairs(digits)
+            .into_iter()
+            .map(|(number, word)| format!("{} {}", number, word))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// Which consonant sounds stand for each digit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mapping {
+    sounds: [Vec<String>; 10],
+}
+
+impl Default for Mapping {
+    fn default() -> Self {
+        Self {
+            sounds: DEFAULT_SOUNDS.map(|sounds| sounds.split(' ').map(String::from).collect()),
+        }
+    }
+}
+
+impl Mapping {
+    /// A mapping from the sounds of every digit, each line separated by spaces.
+    pub fn with_sounds(lines: &[impl AsRef<str>]) -> Result<Self, String> {
+        if lines.len() != 10 {
+            return Err(format!(
+                "expected sounds for 10 digits, got {}",
+                lines.len()
+            ));
+        }
+        let mut mapping = Self {
+            sounds: Default::default(),
+        };
+        for (digit, line) in lines.iter().enumerate() {
+            mapping.set(digit, line.as_ref())?;
+        }
+        Ok(mapping)
+    }
+
+    pub fn sounds(&self, digit: usize) -> &[String] {
+        &self.sounds[digit]
+    }
+
+    /// Assigns `sounds`, separated by spaces, to `digit`. A sound may only
+    /// stand for one digit.
+    pub fn set(&mut self, digit: usize, sounds: &str) -> Result<(), String> {
+        let sounds: Vec<String> = sounds.split_whitespace().map(str::to_lowercase).collect();
+        if digit > 9 {
+            return Err(format!("{} is not a digit", digit));
+        }
+        if sounds.is_empty() {
+            return Err(format!("{} needs at least one sound", digit));
+        }
+        if let Some(sound) = sounds
+            .iter()
+            .find(|sound| !sound.chars().all(|c| c.is_ascii_lowercase()))
+        {
+            return Err(format!("“{}” is not made of letters", sound));
+        }
+        for (other, taken) in self.sounds.iter().enumerate() {
+            if let Some(sound) = sounds
+                .iter()
+                .find(|sound| other != digit && taken.contains(sound))
+            {
+                return Err(format!("“{}” already stands for {}", sound, other));
+            }
+        }
+        self.sounds[digit] = sounds;
+        Ok(())
+    }
+
+    /// The digits spelled by `text`, word by word. The longest sound wins
+    /// (`sh` over `s`) and a doubled letter counts once, so `bell` is 95.
+    pub fn digits(&self, text: &str) -> String {
+        let mut digits = String::new();
+        for word in text.to_lowercase().split_whitespace() {
+            let mut rest = word;
+            let mut previous = None;
+            while let Some(letter) = rest.chars().next() {
+                if previous == Some(letter) {
+                    rest = &rest[letter.len_utf8()..];
+                    continue;
+                }
+                let found = (0..10)
+                    .flat_map(|digit| self.sounds[digit].iter().map(move |sound| (digit, sound)))
+                    .filter(|(_, sound)| rest.starts_with(sound.as_str()))
+                    .max_by_key(|(_, sound)| sound.len());
+                let length = match found {
+                    Some((digit, sound)) => {
+                        digits.push(char::from(b'0' + digit as u8));
+                        sound.len()
+                    }
+                    None => letter.len_utf8(),
+                };
+                previous = rest[..length].chars().last();
+                rest = &rest[length..];
+            }
+        }
+        digits
+    }
+
+    /// The sounds of each digit of `digits`, such as `3 = m · 1 = t/d`.
+    pub fn describe(&self, digits: &str) -> String {
+        digits
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|digit| format!("{} = {}", digit, self.sounds[digit as usize].join("/")))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// The default mapping with the changes saved in `state`.
+fn mapping_of(state: &GameState) -> Mapping {
+    let lines: Vec<&str> = (0..10)
+        .map(|digit| {
+            state
+                .entries
+                .get(&format!("{}{}", SOUNDS, digit))
+                .map_or(DEFAULT_SOUNDS[digit], String::as_str)
+        })
+        .collect();
+    // Saved sounds were checked when set, so this only fails on a damaged save.
+    Mapping::with_sounds(&lines).unwrap_or_default()
+}
+
+/// Entries holding changed sounds, by digit, and the player's own peg words, by number.
+const SOUNDS: &str = "sounds.";
+const PEG: &str = "peg.";
+/// The [`Drill`] practised, by [`Drill::code`].
+const DRILL: &str = "drill";
+
+/// Shortest and longest numbers asked in [`Drill::Numbers`].
+const NUMBER_LENGTHS: std::ops::RangeInclusive<usize> = 4..=6;
+
+/// What the rounds ask.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Drill {
+    /// A number from 00 to 99, answered with any word that spells it.
+    #[default]
+    Pegs,
+    /// A peg word, answered with its number.
+    Words,
+    /// A longer number, answered with words that spell it.
+    Numbers,
+}
+
+impl Drill {
+    pub const ALL: [Drill; 3] = [Drill::Pegs, Drill::Words, Drill::Numbers];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Drill::Pegs => "Number → peg word",
+            Drill::Words => "Peg word → number",
+            Drill::Numbers => "Long number → words",
+        }
+    }
+
+    /// Stored in the counters of the game.
+    pub fn code(self) -> u32 {
+        match self {
+            Drill::Pegs => 0,
+            Drill::Words => 1,
+            Drill::Numbers => 2,
+        }
+    }
+
+    fn from_code(code: u32) -> Self {
+        Drill::ALL
+            .into_iter()
+            .find(|drill| drill.code() == code)
+            .unwrap_or_default()
+    }
+}
+
+/// The Major System: digits become consonant sounds, and the sounds words
+/// that are easier to picture than the numbers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MajorSystem {
+    question: Question,
+    /// Digits the current round is about.
+    number: String,
+    answer: String,
+    state: GameState,
+    /// Sounds being edited, one line per digit.
+    sound_edits: Vec<String>,
+    peg_number: String,
+    peg_word: String,
+    status: Option<String>,
+    #[serde(skip, default = "super::fresh_rng")]
+    rng: GameRng,
+    #[serde(skip)]
+    clock: Clock,
+}
+
+impl MajorSystem {
+    pub fn new() -> Self {
+        let mut module = Self {
+            question: Question::default(),
+            number: String::new(),
+            answer: String::new(),
+            state: GameState {
+                score: 0,
+                attempts: 0,
+                description: "Turn numbers into words with the Major System".into(),
+                ..Default::default()
+            },
+            sound_edits: Vec::new(),
+            peg_number: String::new(),
+            peg_word: String::new(),
+            status: None,
+            rng: super::fresh_rng(),
+            clock: Clock::System,
+        };
+        module.next_round();
+        module
+    }
+
+    /// The default mapping with the player's changes.
+    pub fn mapping(&self) -> Mapping {
+        mapping_of(&self.state)
+    }
+
+    /// Replaces the sounds of every digit; see [`Mapping::with_sounds`].
+    /// The player's peg words that no longer spell their number are dropped,
+    /// and how many were is returned.
+    pub fn set_mapping(&mut self, lines: &[impl AsRef<str>]) -> Result<usize, String> {
+        let mapping = Mapping::with_sounds(lines)?;
+        for digit in 0..10 {
+            let key = format!("{}{}", SOUNDS, digit);
+            if mapping.sounds(digit) == Mapping::default().sounds(digit) {
+                self.state.entries.remove(&key);
+            } else {
+                self.state
+                    .entries
+                    .insert(key, mapping.sounds(digit).join(" "));
+            }
+        }
+        let pegs = self.state.entries.len();
+        self.state.entries.retain(|key, word| {
+            key.strip_prefix(PEG)
+                .is_none_or(|number| mapping.digits(word) == number)
+        });
+        self.sound_edits.clear();
+        self.next_round();
+        Ok(pegs - self.state.entries.len())
+    }
+
+    /// Changes the sounds of `digit`, separated by spaces.
+    pub fn set_sounds(&mut self, digit: usize, sounds: &str) -> Result<usize, String> {
+        let mapping = self.mapping();
+        let mut lines: Vec<String> = (0..10).map(|d| mapping.sounds(d).join(" ")).collect();
+        *lines
+            .get_mut(digit)
+            .ok_or_else(|| format!("{} is not a digit", digit))? = sounds.to_string();
+        self.set_mapping(&lines)
+    }
+
+    pub fn pegs(&self) -> Pegs {
+        Pegs::of(&self.state)
+    }
+
+    /// The player's peg word for `number`, or the bundled one, unless
+    /// neither spells it under the current sounds.
+    pub fn peg(&self, number: usize) -> Option<String> {
+        self.pegs().word(number).map(String::from)
+    }
+
+    /// Replaces the peg word for `number`, which has to spell it.
+    pub fn set_peg(&mut self, number: usize, word: &str) -> Result<(), String> {
+        let word = word.trim().to_lowercase();
+        if number > 99 {
+            return Err(format!("pegs go from 00 to 99, not {}", number));
+        }
+        let spelled = self.mapping().digits(&word);
+        if spelled != format!("{:02}", number) {
+            return Err(format!(
+                "“{}” spells {:?}, not {:02}",
+                word, spelled, number
+            ));
+        }
+        let key = format!("{}{:02}", PEG, number);
+        if word == PEGS[number] {
+            self.state.entries.remove(&key);
+        } else {
+            self.state.entries.insert(key, word);
+        }
+        Ok(())
+    }
+
+    pub fn drill(&self) -> Drill {
+        Drill::from_code(self.state.counters.get(DRILL).copied().unwrap_or(0))
+    }
+
+    /// Switches what the rounds ask, starting a new round.
+    pub fn set_drill(&mut self, drill: Drill) {
+        self.state.counters.insert(DRILL.into(), drill.code());
+        self.next_round();
+    }
+
+    fn pegs_question(&mut self) {
+        let items: Vec<String> = (0..100).map(|number| format!("{:02}", number)).collect();
+        let next = self.state.schedule.pick(
+            items.iter().map(String::as_str),
+            self.clock.now(),
+            &mut self.rng,
+        );
+        let number = next.unwrap_or(0);
+        self.number = items[number].clone();
+        let sounds = self.mapping().describe(&self.number);
+        let (peg, explanation) = match self.peg(number) {
+            Some(peg) => (
+                peg.clone(),
+                format!("{}: {}; any word with those sounds counts", sounds, peg),
+            ),
+            None => (
+                sounds.clone(),
+                format!("{}: any word with those sounds counts", sounds),
+            ),
+        };
+        self.question = Question::new(
+            format!("Peg word for {}", self.number),
+            QuestionKind::Text,
+            vec![peg],
+        )
+        .with_item(self.number.clone())
+        .with_hint(sounds)
+        .with_explanation(explanation);
+    }
+
+    /// Only pegs that spell their number under the current sounds are asked.
+    fn words_question(&mut self) {
+        let pegs = self.pegs();
+        let pegs: Vec<(usize, &str)> = (0..100)
+            .filter_map(|number| Some((number, pegs.word(number)?)))
+            .collect();
+        let next = self.state.schedule.pick(
+            pegs.iter().map(|&(_, word)| word),
+            self.clock.now(),
+            &mut self.rng,
+        );
+        let Some(&(number, peg)) = next.and_then(|index| pegs.get(index)) else {
+            // Sounds that leave no peg at all; fall back to asking for pegs.
+            return self.pegs_question();
+        };
+        self.number = format!("{:02}", number);
+        self.question = Question::new(
+            format!("Which number does “{}” stand for?", peg),
+            QuestionKind::Digits,
+            vec![self.number.clone()],
+        )
+        .with_item(peg.to_string())
+        .with_hint("Two digits; only the consonant sounds count")
+        .with_explanation(format!(
+            "{} is {}: {}",
+            peg,
+            self.number,
+            self.mapping().describe(&self.number)
+        ));
+    }
+
+    fn numbers_question(&mut self) {
+        let length = self.rng.gen_range(NUMBER_LENGTHS);
+        self.number = (0..length)
+            .map(|_| char::from(b'0' + self.rng.gen_range(0..10u8)))
+            .collect();
+        let pegs = self.pegs();
+        let solution: Vec<String> = pegs
+            .pairs(&self.number)
+            .into_iter()
+            .map(|(_, word)| word)
+            .collect();
+        self.question = Question::new(
+            format!("Turn {} into words", self.number),
+            QuestionKind::Text,
+            vec![solution.join(" ")],
+        )
+        .with_hint(self.mapping().describe(&self.number))
+        .with_explanation(pegs.mnemonic(&self.number));
+    }
+}
+
+impl Default for MajorSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameEngine for MajorSystem {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn name(&self) -> &str {
+        "Major System"
+    }
+
+    fn description(&self) -> &str {
+        &self.state.description
+    }
+
+    fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut GameState {
+        &mut self.state
+    }
+
+    /// Starts the reviews over but keeps the mapping and peg words.
+    fn reset(&mut self) {
+        self.state.attempts = 0;
+        self.state.score = 0;
+        self.state.schedule = Default::default();
+        self.next_round();
+    }
+
+    fn next_round(&mut self) {
+        match self.drill() {
+            Drill::Pegs => self.pegs_question(),
+            Drill::Words => self.words_question(),
+            Drill::Numbers => self.numbers_question(),
+        }
+        self.answer.clear();
+    }
+
+    fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+        self.next_round();
+    }
+
+    fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    fn question(&self) -> &Question {
+        &self.question
+    }
+
+    /// Words are graded by what they spell, so any word with the right
+    /// sounds counts, not only the peg.
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
+        self.state.attempts += 1;
+        let points = (self.number.len() as i32 / 2).max(2);
+        let outcome = match self.drill() {
+            Drill::Words if self.question.accepts(&answer.text) => {
+                Outcome::graded(Verdict::Correct, points, &self.question, answer)
+            }
+            Drill::Words => Outcome::graded(Verdict::Wrong, -1, &self.question, answer),
+            Drill::Pegs | Drill::Numbers => {
+                let spelled = self.mapping().digits(&answer.text);
+                if spelled == self.number {
+                    Outcome::graded(Verdict::Correct, points, &self.question, answer)
+                } else {
+                    let explanation = format!(
+                        "“{}” spells {}. {}",
+                        answer.text.trim(),
+                        if spelled.is_empty() {
+                            "nothing"
+                        } else {
+                            &spelled
+                        },
+                        self.question.explanation.clone().unwrap_or_default()
+                    );
+                    Outcome::graded(Verdict::Wrong, -1, &self.question, answer)
+                        .with_explanation(explanation)
+                }
+            }
+        };
+        self.state.score += outcome.points;
+        self.state
+            .schedule
+            .record(&self.question, &outcome, self.clock.now());
+        outcome
+    }
+}
+
+impl GameModule for MajorSystem {
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
+        ui.heading("Major System");
+
+        let mut drill = self.drill();
+        egui::ComboBox::from_label("Drill")
+            .selected_text(drill.label())
+            .show_ui(ui, |ui| {
+                for option in Drill::ALL {
+                    ui.selectable_value(&mut drill, option, option.label());
+                }
+            });
+        if drill != self.drill() {
+            self.set_drill(drill);
+        }
+
+        ui.label(egui::RichText::new(&self.question.prompt).size(24.0));
+        let hint = match drill {
+            Drill::Words => "Type the number, like 31",
+            _ => "Type words whose consonants spell the number",
+        };
+        ui.add(egui::TextEdit::singleline(&mut self.answer))
+            .on_hover_text(hint);
+        let submitted = ui.button("Check").clicked();
+
+        ui.collapsing("Sounds", |ui| {
+            let mapping = self.mapping();
+            if self.sound_edits.len() != 10 {
+                self.sound_edits = (0..10)
+                    .map(|digit| mapping.sounds(digit).join(" "))
+                    .collect();
+            }
+            egui::Grid::new("major_sounds").show(ui, |ui| {
+                for (digit, sounds) in self.sound_edits.iter_mut().enumerate() {
+                    ui.label(digit.to_string());
+                    ui.text_edit_singleline(sounds);
+                    ui.end_row();
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Save sounds").clicked() {
+                    let edits = self.sound_edits.clone();
+                    self.status = Some(match self.set_mapping(&edits) {
+                        Ok(0) => "✓ Sounds saved".into(),
+                        Ok(dropped) => format!(
+                            "✓ Sounds saved; {} peg words no longer spelled their number and were dropped",
+                            dropped
+                        ),
+                        Err(err) => format!("⚠ {}", err),
+                    });
+                }
+                if ui.button("Classic sounds").clicked() {
+                    let _ = self.set_mapping(&DEFAULT_SOUNDS);
+                    self.status = None;
+                }
+            });
+        });
+
+        ui.collapsing("Peg words", |ui| {
+            egui::Grid::new("major_pegs").show(ui, |ui| {
+                for number in 0..100 {
+                    let peg = self.peg(number).unwrap_or_else(|| "—".into());
+                    ui.label(format!("{:02} {}", number, peg));
+                    if number % 10 == 9 {
+                        ui.end_row();
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Number");
+                ui.add(egui::TextEdit::singleline(&mut self.peg_number).desired_width(30.0));
+                ui.label("Word");
+                ui.text_edit_singleline(&mut self.peg_word);
+                if ui.button("Set").clicked() {
+                    let result = match self.peg_number.trim().parse::<usize>() {
+                        Ok(number) => self.set_peg(number, &self.peg_word.clone()),
+                        Err(_) => Err("type a number from 00 to 99".into()),
+                    };
+                    self.status = Some(match result {
+                        Ok(()) => {
+                            self.peg_word.clear();
+                            "✓ Peg word saved".into()
+                        }
+                        Err(err) => format!("⚠ {}", err),
+                    });
+                }
+            });
+        });
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+
+        ui.label(format!("Score: {}", self.state.score));
+        ui.label(format!("Attempts: {}", self.state.attempts));
+        submitted.then(|| std::mem::take(&mut self.answer))
+    }
+}
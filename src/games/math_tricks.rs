@@ -1,43 +1,48 @@
-use rand::{seq::SliceRandom, Rng};
+use rand::seq::SliceRandom;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use super::{GameModule, GameState};
+use super::{
+    Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question, QuestionKind,
+    Verdict,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MathTricks {
-    expression: String,
+    question: Question,
     answer: String,
-    expected: i32,
     state: GameState,
+    #[serde(skip, default = "super::fresh_rng")]
+    rng: GameRng,
 }
 
 impl MathTricks {
     pub fn new() -> Self {
         let mut module = Self {
-            expression: String::new(),
+            question: Question::default(),
             answer: String::new(),
-            expected: 0,
             state: GameState {
                 score: 0,
                 attempts: 0,
                 description: "Recall math tricks quickly".into(),
+                ..Default::default()
             },
+            rng: super::fresh_rng(),
         };
         module.reset();
         module
     }
 
-    fn generate_expression() -> (String, i32) {
-        let mut rng = rand::thread_rng();
+    fn generate_expression(rng: &mut impl Rng) -> (String, i32) {
         let a = rng.gen_range(2..12);
         let b = rng.gen_range(2..12);
         let c = rng.gen_range(1..6);
-        let exprs = vec![
+        let exprs = [
             (format!("({} × {}) + {}", a, b, c), a * b + c),
             (format!("{}² + {}", a, c), a * a + c),
             (format!("{}! / {}", a, b), (2..=a).product::<i32>() / b.max(1)),
         ];
-        exprs.choose(&mut rng).cloned().unwrap_or(exprs[0].clone())
+        exprs.choose(rng).cloned().unwrap_or(exprs[0].clone())
     }
 }
 
@@ -47,7 +52,11 @@ impl Default for MathTricks {
     }
 }
 
-impl GameModule for MathTricks {
+impl GameEngine for MathTricks {
+    fn id(&self) -> &'static str {
+        "math_tricks"
+    }
+
     fn name(&self) -> &str {
         "Math Tricks"
     }
@@ -65,35 +74,54 @@ impl GameModule for MathTricks {
     }
 
     fn reset(&mut self) {
-        let (expr, expected) = Self::generate_expression();
-        self.expression = expr;
-        self.expected = expected;
+        self.next_round();
+    }
+
+    fn next_round(&mut self) {
+        let (expr, expected) = Self::generate_expression(&mut self.rng);
+        self.question = Question::new(
+            format!("Expression: {}", expr),
+            QuestionKind::Number,
+            vec![expected.to_string()],
+        )
+        .with_explanation(format!("{} = {}", expr, expected));
         self.answer.clear();
     }
 
-    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) {
+    fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+        self.next_round();
+    }
+
+    fn question(&self) -> &Question {
+        &self.question
+    }
+
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
+        self.state.attempts += 1;
+        let outcome = if self.question.accepts(&answer.text) {
+            Outcome::graded(Verdict::Correct, 3, &self.question, answer)
+        } else if answer.text.trim().parse::<i32>().is_ok() {
+            Outcome::graded(Verdict::Wrong, -1, &self.question, answer)
+        } else {
+            Outcome::graded(Verdict::Wrong, 0, &self.question, answer)
+        };
+        self.state.score += outcome.points;
+        outcome
+    }
+}
+
+impl GameModule for MathTricks {
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
         ui.heading("Math Tricks");
         ui.label("Solve quickly and input the result:");
-        ui.label(format!("Expression: {}", self.expression));
+        ui.label(&self.question.prompt);
         ui.add(egui::TextEdit::singleline(&mut self.answer));
 
-        if ui.button("Check").clicked() {
-            self.check_answer();
-        }
+        let submitted = ui.button("Check").clicked();
 
         ui.label(format!("Score: {}", self.state.score));
         ui.label(format!("Attempts: {}", self.state.attempts));
-    }
-
-    fn check_answer(&mut self) {
-        self.state.attempts += 1;
-        if let Ok(value) = self.answer.trim().parse::<i32>() {
-            if value == self.expected {
-                self.state.score += 3;
-            } else {
-                self.state.score -= 1;
-            }
-        }
-        self.reset();
+        submitted.then(|| std::mem::take(&mut self.answer))
     }
 }
@@ -5,33 +5,128 @@ pub mod math_tricks;
 pub mod physics_formulas;
 pub mod periodic_table;
 pub mod crypto_puzzle;
+pub mod pattern_memory;
+pub mod constant_challenge;
+pub mod major_system;
+mod recall;
+mod round;
+
+pub(crate) use recall::PEAK_COUNTERS;
+pub use recall::{RecallMode, Sequence};
+pub use round::{Answer, Mark, Outcome, Question, QuestionKind, Study, Verdict};
+
+use std::collections::BTreeMap;
+use std::time::Instant;
 
 use eframe::egui::Ui;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+use crate::scheduler::{Clock, Scheduler};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct GameState {
     pub score: i32,
     pub attempts: i32,
     pub description: String,
+    /// Review schedule for item-based games; empty for the others.
+    #[serde(default)]
+    pub schedule: Scheduler,
+    /// Named numbers a game keeps between sessions, such as a sequence
+    /// length, a recall frontier or a chosen chunk size.
+    #[serde(default)]
+    pub counters: BTreeMap<String, u32>,
+    /// Named text a game keeps between sessions, such as player-supplied sequences.
+    #[serde(default)]
+    pub entries: BTreeMap<String, String>,
 }
 
-impl Default for GameState {
-    fn default() -> Self {
-        Self {
-            score: 0,
-            attempts: 0,
-            description: String::new(),
-        }
-    }
+/// Random source every game draws its rounds from.
+pub type GameRng = StdRng;
+
+/// An unpredictable generator, used until a game is given another one.
+pub fn fresh_rng() -> GameRng {
+    GameRng::from_entropy()
 }
 
-pub trait GameModule {
+pub fn seeded_rng(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed)
+}
+
+/// Every kata, in main menu order.
+pub fn all() -> Vec<Box<dyn GameModule + Send + Sync>> {
+    vec![
+        Box::new(pi_challenge::PiChallenge::new()),
+        Box::new(greek_alphabet::GreekAlphabet::new()),
+        Box::new(music_tempo::MusicTempo::new()),
+        Box::new(math_tricks::MathTricks::new()),
+        Box::new(physics_formulas::PhysicsFormulas::new()),
+        Box::new(periodic_table::PeriodicTable::new()),
+        Box::new(crypto_puzzle::CryptoPuzzle::new()),
+        Box::new(pattern_memory::PatternMemory::new()),
+        Box::new(constant_challenge::ConstantChallenge::new()),
+        Box::new(major_system::MajorSystem::new()),
+    ]
+}
+
+/// UI-free core of a kata: round generation, answer checking and scoring.
+///
+/// Everything needed to play a game lives here, so games can be driven from
+/// tests, scripts or any front-end without an eframe window.
+pub trait GameEngine {
+    /// Stable identifier used to key saved progress; never change it once shipped.
+    fn id(&self) -> &'static str;
     fn name(&self) -> &str;
     fn description(&self) -> &str;
     fn state(&self) -> &GameState;
     fn state_mut(&mut self) -> &mut GameState;
     fn reset(&mut self);
-    fn update(&mut self, ui: &mut Ui, frame: &eframe::Frame);
-    fn check_answer(&mut self);
+    /// Moves on to the following round once the player has seen the feedback.
+    fn next_round(&mut self);
+    /// The round the player is currently asked to answer.
+    fn question(&self) -> &Question;
+    /// Grades `answer` against the current round and updates the score.
+    ///
+    /// The round stays in place until [`GameEngine::next_round`] is called.
+    fn check_answer(&mut self, answer: &Answer) -> Outcome;
+
+    /// Replaces the random source every round is drawn from, then picks a
+    /// new round with it. Games without randomness ignore it.
+    fn set_rng(&mut self, _rng: GameRng) {}
+
+    /// Restarts the random sequence so that, from the same saved progress,
+    /// the same seed asks the same rounds.
+    fn reseed(&mut self, seed: u64) {
+        self.set_rng(seeded_rng(seed));
+    }
+
+    /// Sets the clock review schedules are kept against; a frontend stops
+    /// it for a seeded session. Games without a schedule ignore it.
+    fn set_clock(&mut self, _clock: Clock) {}
+
+    /// Continues from saved progress; the description stays the one of this build.
+    fn restore(&mut self, mut state: GameState) {
+        state.description = std::mem::take(&mut self.state_mut().description);
+        *self.state_mut() = state;
+        // Re-pick the opening round now that the saved review schedule is known.
+        self.next_round();
+    }
+
+    /// Looks at the saved progress of every game, for games that build on
+    /// another one: the digit games show the player's own Major System pegs.
+    /// Ignored by default.
+    fn share_progress(&mut self, _games: &BTreeMap<String, GameState>) {}
+}
+
+/// egui front-end layered on top of a [`GameEngine`].
+pub trait GameModule: GameEngine {
+    /// Draws the current round and returns the player's answer once submitted.
+    fn update(&mut self, ui: &mut Ui, frame: &eframe::Frame) -> Option<String>;
+
+    /// When the study material of the current round was hidden, for games
+    /// that show some before asking. Answers are timed from then.
+    fn study_ended(&self) -> Option<Instant> {
+        None
+    }
 }
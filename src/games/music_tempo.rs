@@ -1,25 +1,34 @@
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
-use super::{GameModule, GameState};
+use super::{
+    Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question, QuestionKind,
+    Verdict,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MusicTempo {
     target_bpm: u16,
+    question: Question,
     answer: String,
     state: GameState,
+    #[serde(skip, default = "super::fresh_rng")]
+    rng: GameRng,
 }
 
 impl MusicTempo {
     pub fn new() -> Self {
         let mut module = Self {
             target_bpm: 0,
+            question: Question::default(),
             answer: String::new(),
             state: GameState {
                 score: 0,
                 attempts: 0,
                 description: "Match tempo markings to BPM".into(),
+                ..Default::default()
             },
+            rng: super::fresh_rng(),
         };
         module.reset();
         module
@@ -43,7 +52,11 @@ impl Default for MusicTempo {
     }
 }
 
-impl GameModule for MusicTempo {
+impl GameEngine for MusicTempo {
+    fn id(&self) -> &'static str {
+        "music_tempo"
+    }
+
     fn name(&self) -> &str {
         "Music Tempo"
     }
@@ -61,37 +74,66 @@ impl GameModule for MusicTempo {
     }
 
     fn reset(&mut self) {
-        let mut rng = rand::thread_rng();
-        self.target_bpm = rng.gen_range(40..=200);
+        self.next_round();
+    }
+
+    fn next_round(&mut self) {
+        self.target_bpm = self.rng.gen_range(40..=200);
+        self.question = Question::new(
+            format!("Tempo hint: {}", Self::tempo_hint(self.target_bpm)),
+            QuestionKind::Number,
+            vec![self.target_bpm.to_string()],
+        )
+        .with_hint("Within 5 BPM scores full points, within 15 scores partial")
+        .with_explanation(format!(
+            "{} BPM is marked {}",
+            self.target_bpm,
+            Self::tempo_hint(self.target_bpm)
+        ));
         self.answer.clear();
     }
 
-    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) {
+    fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+        self.next_round();
+    }
+
+    fn question(&self) -> &Question {
+        &self.question
+    }
+
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
+        self.state.attempts += 1;
+        let question = &self.question;
+        let outcome = match answer.text.trim().parse::<i32>() {
+            Ok(value) => {
+                let diff = (value - self.target_bpm as i32).abs();
+                if diff <= 5 {
+                    Outcome::graded(Verdict::Correct, 5, question, answer)
+                } else if diff <= 15 {
+                    Outcome::graded(Verdict::Partial, 2, question, answer)
+                } else {
+                    Outcome::graded(Verdict::Wrong, -1, question, answer)
+                }
+            }
+            Err(_) => Outcome::graded(Verdict::Wrong, 0, question, answer),
+        };
+        self.state.score += outcome.points;
+        outcome
+    }
+}
+
+impl GameModule for MusicTempo {
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
         ui.heading("Music Tempo Trainer");
-        ui.label(format!("Tempo hint: {}", Self::tempo_hint(self.target_bpm)));
+        ui.label(&self.question.prompt);
         ui.label("Guess the BPM:");
         ui.add(egui::TextEdit::singleline(&mut self.answer));
 
-        if ui.button("Check").clicked() {
-            self.check_answer();
-        }
+        let submitted = ui.button("Check").clicked();
 
         ui.label(format!("Score: {}", self.state.score));
         ui.label(format!("Attempts: {}", self.state.attempts));
-    }
-
-    fn check_answer(&mut self) {
-        self.state.attempts += 1;
-        if let Ok(value) = self.answer.trim().parse::<i32>() {
-            let diff = (value - self.target_bpm as i32).abs();
-            if diff <= 5 {
-                self.state.score += 5;
-            } else if diff <= 15 {
-                self.state.score += 2;
-            } else {
-                self.state.score -= 1;
-            }
-        }
-        self.reset();
+        submitted.then(|| std::mem::take(&mut self.answer))
     }
 }
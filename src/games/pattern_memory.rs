@@ -0,0 +1,293 @@
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question, QuestionKind, Verdict,
+};
+
+/// Each alphabet pairs every symbol with the key typed to enter it.
+const COLOURS: &[(&str, char)] = &[
+    ("🟥", 'r'),
+    ("🟧", 'o'),
+    ("🟨", 'y'),
+    ("🟩", 'g'),
+    ("🟦", 'b'),
+    ("🟪", 'p'),
+];
+const DIGITS: &[(&str, char)] = &[
+    ("0", '0'),
+    ("1", '1'),
+    ("2", '2'),
+    ("3", '3'),
+    ("4", '4'),
+    ("5", '5'),
+    ("6", '6'),
+    ("7", '7'),
+    ("8", '8'),
+    ("9", '9'),
+];
+const SYMBOLS: &[(&str, char)] = &[
+    ("♠", 's'),
+    ("♥", 'h'),
+    ("♦", 'd'),
+    ("♣", 'c'),
+    ("★", 't'),
+    ("♪", 'n'),
+];
+
+const START_LENGTH: u32 = 3;
+const MAX_LENGTH: u32 = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum Alphabet {
+    Colours,
+    Digits,
+    Symbols,
+}
+
+impl Alphabet {
+    const ALL: [Alphabet; 3] = [Alphabet::Colours, Alphabet::Digits, Alphabet::Symbols];
+
+    fn name(self) -> &'static str {
+        match self {
+            Alphabet::Colours => "colours",
+            Alphabet::Digits => "digits",
+            Alphabet::Symbols => "symbols",
+        }
+    }
+
+    fn symbols(self) -> &'static [(&'static str, char)] {
+        match self {
+            Alphabet::Colours => COLOURS,
+            Alphabet::Digits => DIGITS,
+            Alphabet::Symbols => SYMBOLS,
+        }
+    }
+
+    /// Key under which the sequence length of this alphabet is saved.
+    fn counter(self) -> String {
+        format!("length.{}", self.name())
+    }
+
+    /// Reads a typed answer as keys, accepting both the symbols and their keys.
+    fn keys(self, text: &str) -> String {
+        let symbols = self.symbols();
+        let mut keys = String::new();
+        let mut rest = text.trim();
+        while let Some(c) = rest.chars().next() {
+            let c_lower = c.to_ascii_lowercase();
+            if let Some(&(symbol, key)) =
+                symbols.iter().find(|(symbol, _)| rest.starts_with(symbol))
+            {
+                keys.push(key);
+                rest = &rest[symbol.len()..];
+            } else {
+                if symbols.iter().any(|&(_, key)| key == c_lower) {
+                    keys.push(c_lower);
+                } else if !(c.is_whitespace() || c == ',') {
+                    keys.push('?');
+                }
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        keys
+    }
+}
+
+/// Flashes a sequence of colours, digits or symbols, hides it and asks for it
+/// back. Every alphabet has its own length, growing by one after each
+/// perfect recall and shrinking after a miss.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PatternMemory {
+    question: Question,
+    alphabet: Alphabet,
+    /// Keys of the sequence being asked.
+    keys: String,
+    answer: String,
+    state: GameState,
+    /// When the sequence is hidden, set the first time it is drawn.
+    #[serde(skip)]
+    hide_at: Option<Instant>,
+    #[serde(skip, default = "super::fresh_rng")]
+    rng: GameRng,
+}
+
+impl PatternMemory {
+    pub fn new() -> Self {
+        let mut module = Self {
+            question: Question::default(),
+            alphabet: Alphabet::Colours,
+            keys: String::new(),
+            answer: String::new(),
+            state: GameState {
+                score: 0,
+                attempts: 0,
+                description: "Recall flashed colour, number and symbol sequences".into(),
+                ..Default::default()
+            },
+            hide_at: None,
+            rng: super::fresh_rng(),
+        };
+        module.reset();
+        module
+    }
+
+    fn length(&self, alphabet: Alphabet) -> u32 {
+        self.state
+            .counters
+            .get(&alphabet.counter())
+            .copied()
+            .unwrap_or(START_LENGTH)
+    }
+
+    fn set_length(&mut self, alphabet: Alphabet, length: u32) {
+        let length = length.clamp(START_LENGTH, MAX_LENGTH);
+        self.state.counters.insert(alphabet.counter(), length);
+    }
+
+    /// Time the sequence stays visible: a second plus half a second per item.
+    fn study_time(length: u32) -> Duration {
+        Duration::from_millis(1000 + 500 * u64::from(length))
+    }
+}
+
+impl Default for PatternMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameEngine for PatternMemory {
+    fn id(&self) -> &'static str {
+        "pattern_memory"
+    }
+
+    fn name(&self) -> &str {
+        "Pattern Memory"
+    }
+
+    fn description(&self) -> &str {
+        &self.state.description
+    }
+
+    fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut GameState {
+        &mut self.state
+    }
+
+    fn reset(&mut self) {
+        self.state.counters.clear();
+        self.next_round();
+    }
+
+    fn next_round(&mut self) {
+        self.alphabet = Alphabet::ALL[self.rng.gen_range(0..Alphabet::ALL.len())];
+        let symbols = self.alphabet.symbols();
+        let length = self.length(self.alphabet);
+        let sequence: Vec<(&str, char)> = (0..length)
+            .map(|_| symbols[self.rng.gen_range(0..symbols.len())])
+            .collect();
+
+        let shown: Vec<&str> = sequence.iter().map(|&(symbol, _)| symbol).collect();
+        self.keys = sequence.iter().map(|&(_, key)| key).collect();
+        let legend: Vec<String> = symbols
+            .iter()
+            .map(|(symbol, key)| format!("{} {}", symbol, key))
+            .collect();
+        let mut question = Question::new(
+            format!("Repeat the {} {}", length, self.alphabet.name()),
+            QuestionKind::Text,
+            vec![shown.join(" "), self.keys.clone()],
+        )
+        .with_study(shown.join(" "), Self::study_time(length));
+        if self.alphabet != Alphabet::Digits {
+            question = question.with_hint(format!("Keys: {}", legend.join("  ")));
+        }
+        self.question = question;
+        self.answer.clear();
+        self.hide_at = None;
+    }
+
+    fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+        self.next_round();
+    }
+
+    fn question(&self) -> &Question {
+        &self.question
+    }
+
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
+        self.state.attempts += 1;
+        let given = self.alphabet.keys(&answer.text);
+        let matching = given
+            .chars()
+            .zip(self.keys.chars())
+            .filter(|(given, expected)| given == expected)
+            .count();
+        let length = self.length(self.alphabet);
+
+        let placed = format!("{} of {} in the right place", matching, self.keys.len());
+
+        let outcome = if given == self.keys {
+            self.set_length(self.alphabet, length + 1);
+            Outcome::graded(Verdict::Correct, length as i32, &self.question, answer)
+        } else if given.len() == self.keys.len() && matching * 2 >= self.keys.len() {
+            Outcome::graded(Verdict::Partial, 0, &self.question, answer).with_explanation(placed)
+        } else {
+            self.set_length(self.alphabet, length - 1);
+            Outcome::graded(Verdict::Wrong, -1, &self.question, answer).with_explanation(placed)
+        };
+        self.state.score += outcome.points;
+        outcome
+    }
+}
+
+impl GameModule for PatternMemory {
+    fn study_ended(&self) -> Option<Instant> {
+        self.hide_at
+    }
+
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
+        ui.heading("Pattern Memory");
+        let study = self.question.study.clone().unwrap_or_default();
+        let hide_at = *self
+            .hide_at
+            .get_or_insert_with(|| Instant::now() + study.duration);
+        let left = hide_at.saturating_duration_since(Instant::now());
+
+        let mut submitted = false;
+        if !left.is_zero() {
+            ui.label(format!("Memorise these {}:", self.alphabet.name()));
+            ui.label(egui::RichText::new(&study.content).size(36.0));
+            ui.label(format!("Hidden in {:.1}s", left.as_secs_f32()));
+            if ui.button("I'm ready").clicked() {
+                self.hide_at = Some(Instant::now());
+            }
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+        } else {
+            ui.label(&self.question.prompt);
+            ui.horizontal(|ui| {
+                for &(symbol, key) in self.alphabet.symbols() {
+                    if ui.button(egui::RichText::new(symbol).size(24.0)).clicked() {
+                        self.answer.push(key);
+                    }
+                }
+                if ui.button("⌫").clicked() {
+                    self.answer.pop();
+                }
+            });
+            ui.add(egui::TextEdit::singleline(&mut self.answer));
+            submitted = ui.button("Check").clicked();
+        }
+
+        ui.label(format!("Score: {}", self.state.score));
+        ui.label(format!("Attempts: {}", self.state.attempts));
+        submitted.then(|| std::mem::take(&mut self.answer))
+    }
+}
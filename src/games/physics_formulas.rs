@@ -1,8 +1,11 @@
-use rand::seq::SliceRandom;
-use rand::thread_rng;
 use serde::{Deserialize, Serialize};
 
-use super::{GameModule, GameState};
+use crate::scheduler::Clock;
+
+use super::{
+    Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question, QuestionKind,
+    Verdict,
+};
 
 const FORMULAS: &[(&str, &str)] = &[
     ("F = m · a", "Newton's Second Law"),
@@ -14,22 +17,28 @@ const FORMULAS: &[(&str, &str)] = &[
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PhysicsFormulas {
-    #[serde(skip, default)]
-    prompt: Option<(&'static str, &'static str)>,
+    question: Question,
     answer: String,
     state: GameState,
+    #[serde(skip, default = "super::fresh_rng")]
+    rng: GameRng,
+    #[serde(skip)]
+    clock: Clock,
 }
 
 impl PhysicsFormulas {
     pub fn new() -> Self {
         let mut module = Self {
-            prompt: None,
+            question: Question::default(),
             answer: String::new(),
             state: GameState {
                 score: 0,
                 attempts: 0,
                 description: "Recall famous physics formulas".into(),
+                ..Default::default()
             },
+            rng: super::fresh_rng(),
+            clock: Clock::System,
         };
         module.reset();
         module
@@ -42,7 +51,11 @@ impl Default for PhysicsFormulas {
     }
 }
 
-impl GameModule for PhysicsFormulas {
+impl GameEngine for PhysicsFormulas {
+    fn id(&self) -> &'static str {
+        "physics_formulas"
+    }
+
     fn name(&self) -> &str {
         "Physics Formulas"
     }
@@ -60,36 +73,64 @@ impl GameModule for PhysicsFormulas {
     }
 
     fn reset(&mut self) {
-        let mut rng = thread_rng();
-        self.prompt = FORMULAS.choose(&mut rng).copied();
+        self.next_round();
+    }
+
+    fn next_round(&mut self) {
+        let items = FORMULAS.iter().map(|&(formula, _)| formula);
+        let next = self.state.schedule.pick(items, self.clock.now(), &mut self.rng);
+        if let Some(&(formula, name)) = next.and_then(|index| FORMULAS.get(index)) {
+            self.question = Question::new(
+                format!("Formula: {}", formula),
+                QuestionKind::Text,
+                vec![name.into()],
+            )
+            .with_item(formula)
+            .with_hint(format!("Starts with “{}”", &name[..1]))
+            .with_explanation(format!("{} is known as {}", formula, name));
+        }
         self.answer.clear();
     }
 
-    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) {
+    fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+        self.next_round();
+    }
+
+    fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    fn question(&self) -> &Question {
+        &self.question
+    }
+
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
+        self.state.attempts += 1;
+        let outcome = if self.question.accepts(&answer.text) {
+            Outcome::graded(Verdict::Correct, 2, &self.question, answer)
+        } else {
+            Outcome::graded(Verdict::Wrong, -1, &self.question, answer)
+        };
+        self.state.score += outcome.points;
+        self.state
+            .schedule
+            .record(&self.question, &outcome, self.clock.now());
+        outcome
+    }
+}
+
+impl GameModule for PhysicsFormulas {
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
         ui.heading("Physics Formulas");
-        if let Some((formula, _)) = self.prompt {
-            ui.label(format!("Formula: {}", formula));
-        }
+        ui.label(&self.question.prompt);
         ui.label("Name this formula:");
         ui.add(egui::TextEdit::singleline(&mut self.answer));
 
-        if ui.button("Check").clicked() {
-            self.check_answer();
-        }
+        let submitted = ui.button("Check").clicked();
 
         ui.label(format!("Score: {}", self.state.score));
         ui.label(format!("Attempts: {}", self.state.attempts));
-    }
-
-    fn check_answer(&mut self) {
-        self.state.attempts += 1;
-        if let Some((_, name)) = self.prompt {
-            if self.answer.trim().eq_ignore_ascii_case(name) {
-                self.state.score += 2;
-            } else {
-                self.state.score -= 1;
-            }
-        }
-        self.reset();
+        submitted.then(|| std::mem::take(&mut self.answer))
     }
 }
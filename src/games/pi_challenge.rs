@@ -1,29 +1,65 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
 use serde::{Deserialize, Serialize};
 
-use super::{GameModule, GameState};
+use crate::digits::Constant;
+
+use super::major_system::{self, Pegs};
+use super::recall::{Recall, RecallMode, Sequence};
+use super::{Answer, GameEngine, GameModule, GameRng, GameState, Outcome, Question};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PiChallenge {
-    digits: Vec<char>,
-    input: String,
+    recall: Recall,
     state: GameState,
 }
 
 impl PiChallenge {
     pub fn new() -> Self {
-        Self {
-            digits: "3141592653589793238462643383279".chars().collect(),
-            input: String::new(),
+        let mut module = Self {
+            recall: Recall::new(Sequence::Constant(Constant::Pi), false),
             state: GameState {
                 score: 0,
                 attempts: 0,
                 description: "Memorize digits of π".into(),
+                ..Default::default()
             },
-        }
+        };
+        module.next_round();
+        module
+    }
+
+    /// How many digits of π the player has recalled so far.
+    pub fn frontier(&self) -> usize {
+        self.recall.frontier(&self.state)
+    }
+
+    pub fn mode(&self) -> RecallMode {
+        Recall::mode(&self.state)
+    }
+
+    /// Switches what the rounds ask, starting a new round.
+    pub fn set_mode(&mut self, mode: RecallMode) {
+        Recall::set_mode(&mut self.state, mode);
+        self.next_round();
     }
 
-    fn current_sequence(&self, length: usize) -> String {
-        self.digits.iter().take(length).collect()
+    /// First and last digit, counted from 1, practised outside the frontier mode.
+    pub fn range(&self) -> (usize, usize) {
+        self.recall.range(&self.state)
+    }
+
+    /// Shows Major System peg words next to the chunks being memorised,
+    /// from the next round on.
+    pub fn set_mnemonics(&mut self, on: bool) {
+        Recall::set_mnemonics(&mut self.state, on);
+    }
+
+    /// Sets the range practised, starting a new round.
+    pub fn set_range(&mut self, from: usize, to: usize) {
+        Recall::set_range(&mut self.state, from, to);
+        self.next_round();
     }
 }
 
@@ -33,7 +69,11 @@ impl Default for PiChallenge {
     }
 }
 
-impl GameModule for PiChallenge {
+impl GameEngine for PiChallenge {
+    fn id(&self) -> &'static str {
+        "pi_challenge"
+    }
+
     fn name(&self) -> &str {
         "Π Challenge"
     }
@@ -51,37 +91,46 @@ impl GameModule for PiChallenge {
     }
 
     fn reset(&mut self) {
-        self.input.clear();
         self.state.attempts = 0;
         self.state.score = 0;
+        self.recall.forget(&mut self.state);
+        self.next_round();
     }
 
-    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) {
-        ui.heading("Π Challenge");
-        ui.label("Enter the next digits of π: ∞");
+    fn next_round(&mut self) {
+        self.recall.next_round(&self.state);
+    }
 
-        ui.add(egui::TextEdit::singleline(&mut self.input))
-            .on_hover_text("Type digits like 3.14159...");
+    fn set_rng(&mut self, rng: GameRng) {
+        self.recall.set_rng(rng);
+        self.next_round();
+    }
 
-        if ui.button("Check").clicked() {
-            self.check_answer();
-        }
+    fn share_progress(&mut self, games: &BTreeMap<String, GameState>) {
+        let pegs = games.get(major_system::ID).map(Pegs::of).unwrap_or_default();
+        self.recall.set_pegs(pegs);
+    }
 
-        ui.label(format!("Score: {}", self.state.score));
-        ui.label(format!("Attempts: {}", self.state.attempts));
+    fn question(&self) -> &Question {
+        self.recall.question()
+    }
+
+    fn check_answer(&mut self, answer: &Answer) -> Outcome {
+        self.recall.check_answer(&mut self.state, answer)
     }
+}
 
-    fn check_answer(&mut self) {
-        let len = self.input.len();
-        let target = self.current_sequence(len);
-        self.state.attempts += 1;
+impl GameModule for PiChallenge {
+    fn study_ended(&self) -> Option<Instant> {
+        self.recall.study_ended()
+    }
 
-        if self.input == target {
-            self.state.score += len as i32;
-        } else {
-            self.state.score -= 1;
-        }
+    fn update(&mut self, ui: &mut egui::Ui, _frame: &eframe::Frame) -> Option<String> {
+        ui.heading("Π Challenge");
+        let submitted = self.recall.update(ui, &mut self.state);
 
-        self.input.clear();
+        ui.label(format!("Score: {}", self.state.score));
+        ui.label(format!("Attempts: {}", self.state.attempts));
+        submitted
     }
 }
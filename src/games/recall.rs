@@ -0,0 +1,670 @@
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::digits::{self, Constant};
+
+use super::major_system::Pegs;
+use super::{Answer, GameRng, GameState, Outcome, Question, QuestionKind, Verdict};
+
+/// Digits the player has recalled correctly, from the start of the sequence.
+const FRONTIER: &str = "frontier";
+/// Correct rounds in a row, and the most ever.
+const STREAK: &str = "streak";
+const BEST_STREAK: &str = "best_streak";
+/// Counters that only ever grow, per sequence when scoped, so merged
+/// progress keeps the highest of each.
+pub(crate) const PEAK_COUNTERS: [&str; 2] = [FRONTIER, BEST_STREAK];
+/// Digits shown and asked per round.
+const CHUNK: &str = "chunk";
+/// How long a chunk stays visible; 0 skips straight to recall.
+const STUDY_SECONDS: &str = "study_seconds";
+/// The [`RecallMode`] practised, by [`RecallMode::code`].
+const MODE: &str = "mode";
+/// First and last digit position, counted from 1, of the practice range.
+const RANGE_FROM: &str = "range_from";
+const RANGE_TO: &str = "range_to";
+/// Index of the next chunk asked in [`RecallMode::Range`].
+const RANGE_CURSOR: &str = "range_cursor";
+/// 1 to show Major System peg words next to the chunks being memorised.
+const MNEMONICS: &str = "mnemonics";
+
+const DEFAULT_CHUNK: u32 = 5;
+const DEFAULT_STUDY_SECONDS: u32 = 3;
+const DEFAULT_RANGE: (u32, u32) = (1, 100);
+/// How far past the digits worked out so far the range can be dragged;
+/// reaching further takes a few steps, each computed in the background.
+const RANGE_AHEAD: usize = 5 * digits::BLOCK;
+const MAX_RANGE: usize = 100_000;
+/// Known digits shown before the chunk to anchor it.
+const CONTEXT: usize = 5;
+/// Digits in the block whose position is asked in [`RecallMode::BlockStart`].
+const BLOCK_LENGTH: usize = 6;
+/// Points for finding a block.
+const BLOCK_POINTS: i32 = 3;
+
+/// What the rounds ask.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecallMode {
+    /// The chunk just past the frontier, pushing it on.
+    #[default]
+    Frontier,
+    /// The chunks of the practice range in order, starting over at its end.
+    Range,
+    /// The digits at a random position in the range.
+    DigitsAt,
+    /// The position of a random block of digits from the range.
+    BlockStart,
+}
+
+impl RecallMode {
+    pub const ALL: [RecallMode; 4] = [
+        RecallMode::Frontier,
+        RecallMode::Range,
+        RecallMode::DigitsAt,
+        RecallMode::BlockStart,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RecallMode::Frontier => "Push the frontier",
+            RecallMode::Range => "Practise a range",
+            RecallMode::DigitsAt => "Digits at a position",
+            RecallMode::BlockStart => "Find a block",
+        }
+    }
+
+    /// Stored in the counters of the game.
+    pub fn code(self) -> u32 {
+        match self {
+            RecallMode::Frontier => 0,
+            RecallMode::Range => 1,
+            RecallMode::DigitsAt => 2,
+            RecallMode::BlockStart => 3,
+        }
+    }
+
+    fn from_code(code: u32) -> Self {
+        RecallMode::ALL
+            .into_iter()
+            .find(|mode| mode.code() == code)
+            .unwrap_or_default()
+    }
+}
+
+/// A digit sequence practised chunk by chunk.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Sequence {
+    Constant(Constant),
+    /// Digits supplied by the player, such as a phone or card number.
+    Custom {
+        name: String,
+        digits: String,
+    },
+}
+
+impl Sequence {
+    pub fn label(&self) -> &str {
+        match self {
+            Sequence::Constant(constant) => constant.symbol(),
+            Sequence::Custom { name, .. } => name,
+        }
+    }
+
+    /// Identifies the sequence in the names of its progress counters.
+    pub fn id(&self) -> String {
+        match self {
+            Sequence::Constant(constant) => constant.id().to_string(),
+            Sequence::Custom { name, .. } => format!("custom.{}", name),
+        }
+    }
+
+    /// Number of digits, for sequences that end.
+    fn len(&self) -> Option<usize> {
+        match self {
+            Sequence::Constant(_) => None,
+            Sequence::Custom { digits, .. } => Some(digits.len()),
+        }
+    }
+}
+
+/// Chunked recall shared by the digit games: the chunk just past the
+/// player's frontier is shown for the study time, hidden, then typed back.
+/// The other [`RecallMode`]s drill a chosen range of digits instead.
+///
+/// Progress lives in the counters of the owning game's [`GameState`]; the
+/// frontier and streaks are kept per sequence, the other settings per game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(super) struct Recall {
+    sequence: Sequence,
+    /// Whether counter names carry the sequence id; the Π Challenge only
+    /// ever has one sequence and keeps the plain names.
+    scoped: bool,
+    /// Digits worked out so far, extended a block at a time as the frontier grows.
+    digits: Vec<char>,
+    /// Index of the first digit asked this round.
+    position: usize,
+    /// Drill of the current question, which the settings may since have changed.
+    #[serde(default)]
+    mode: RecallMode,
+    question: Question,
+    input: String,
+    /// When the chunk is hidden, set the first time it is drawn.
+    #[serde(skip)]
+    hide_at: Option<Instant>,
+    #[serde(skip, default = "super::fresh_rng")]
+    rng: GameRng,
+    /// The player's Major System pegs, shown while memorising if enabled.
+    #[serde(skip)]
+    pegs: Pegs,
+    /// Range being edited, applied once the drag or typing is over.
+    #[serde(skip)]
+    range_draft: Option<(usize, usize)>,
+    /// Digits being worked out on another thread before the next round.
+    #[serde(skip)]
+    pending: Option<Arc<OnceLock<String>>>,
+}
+
+impl Recall {
+    pub fn new(sequence: Sequence, scoped: bool) -> Self {
+        let digits = match &sequence {
+            Sequence::Custom { digits, .. } => digits.chars().collect(),
+            Sequence::Constant(_) => Vec::new(),
+        };
+        Self {
+            sequence,
+            scoped,
+            digits,
+            position: 0,
+            mode: RecallMode::Frontier,
+            question: Question::default(),
+            input: String::new(),
+            hide_at: None,
+            rng: super::fresh_rng(),
+            pegs: Pegs::default(),
+            range_draft: None,
+            pending: None,
+        }
+    }
+
+    /// Moves over to `sequence`, keeping the random generator and pegs.
+    pub fn switch_to(&mut self, sequence: Sequence) {
+        let rng = self.rng.clone();
+        let pegs = std::mem::take(&mut self.pegs);
+        *self = Self {
+            rng,
+            pegs,
+            ..Self::new(sequence, self.scoped)
+        };
+    }
+
+    pub fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+    }
+
+    /// Uses `pegs` for the study notes, including the one on screen.
+    pub fn set_pegs(&mut self, pegs: Pegs) {
+        self.pegs = pegs;
+        if let Some(study) = &mut self.question.study {
+            if study.note.is_some() {
+                study.note = Some(self.pegs.mnemonic(&study.content));
+            }
+        }
+    }
+
+    pub fn sequence(&self) -> &Sequence {
+        &self.sequence
+    }
+
+    pub fn question(&self) -> &Question {
+        &self.question
+    }
+
+    /// When the chunk being memorised was hidden, once it has been shown.
+    pub fn study_ended(&self) -> Option<Instant> {
+        self.hide_at
+    }
+
+    fn key(&self, name: &str) -> String {
+        if self.scoped {
+            format!("{}.{}", name, self.sequence.id())
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn counter(state: &GameState, key: &str, default: u32) -> u32 {
+        state.counters.get(key).copied().unwrap_or(default)
+    }
+
+    pub fn frontier(&self, state: &GameState) -> usize {
+        Self::counter(state, &self.key(FRONTIER), 0) as usize
+    }
+
+    pub fn best_streak(&self, state: &GameState) -> u32 {
+        Self::counter(state, &self.key(BEST_STREAK), 0)
+    }
+
+    /// Forgets the frontier and streaks of this sequence.
+    pub fn forget(&self, state: &mut GameState) {
+        for name in [FRONTIER, STREAK, BEST_STREAK] {
+            state.counters.remove(&self.key(name));
+        }
+    }
+
+    pub fn mode(state: &GameState) -> RecallMode {
+        RecallMode::from_code(Self::counter(state, MODE, 0))
+    }
+
+    pub fn set_mode(state: &mut GameState, mode: RecallMode) {
+        state.counters.insert(MODE.into(), mode.code());
+    }
+
+    /// First and last digit position of the practice range, counted from 1
+    /// and cut short at the end of a custom sequence.
+    pub fn range(&self, state: &GameState) -> (usize, usize) {
+        let from = Self::counter(state, RANGE_FROM, DEFAULT_RANGE.0).max(1) as usize;
+        let mut to = (Self::counter(state, RANGE_TO, DEFAULT_RANGE.1) as usize).max(from);
+        if let Some(len) = self.sequence.len() {
+            to = to.min(len);
+        }
+        (from.min(to), to)
+    }
+
+    pub fn set_range(state: &mut GameState, from: usize, to: usize) {
+        let from = from.max(1);
+        state.counters.insert(RANGE_FROM.into(), from as u32);
+        state.counters.insert(RANGE_TO.into(), to.max(from) as u32);
+        state.counters.remove(RANGE_CURSOR);
+    }
+
+    /// Highest position the range can be set to in the settings: the end of
+    /// a custom sequence, or a few blocks past the digits worked out so far.
+    fn range_limit(&self) -> usize {
+        match self.sequence.len() {
+            Some(len) => len,
+            None => (self.digits.len() + RANGE_AHEAD).min(MAX_RANGE),
+        }
+    }
+
+    /// Whether Major System peg words are shown next to the chunks.
+    pub fn set_mnemonics(state: &mut GameState, on: bool) {
+        state.counters.insert(MNEMONICS.into(), u32::from(on));
+    }
+
+    fn study_time(state: &GameState) -> Duration {
+        Duration::from_secs(Self::counter(state, STUDY_SECONDS, DEFAULT_STUDY_SECONDS).into())
+    }
+
+    fn text(&self, start: usize, length: usize) -> String {
+        self.digits.iter().skip(start).take(length).collect()
+    }
+
+    /// Makes sure the digits up to index `end` are known.
+    fn load(&mut self, end: usize) {
+        if let Sequence::Constant(constant) = self.sequence {
+            if self.digits.len() < end {
+                let count = end.div_ceil(digits::BLOCK) * digits::BLOCK;
+                self.digits = constant.digits(count).chars().collect();
+            }
+        }
+    }
+
+    /// `…` and the digits just before `position`, or nothing at the start.
+    fn context(&self, position: usize) -> String {
+        let from = position.saturating_sub(CONTEXT);
+        if from == position {
+            String::new()
+        } else {
+            format!("…{}", self.text(from, position - from))
+        }
+    }
+
+    /// Asks the next round of the mode set in `state`.
+    pub fn next_round(&mut self, state: &GameState) {
+        self.pending = None;
+        self.mode = Self::mode(state);
+        let chunk = Self::counter(state, CHUNK, DEFAULT_CHUNK).max(1) as usize;
+        self.question = match self.mode {
+            RecallMode::Frontier => self.frontier_question(state, chunk),
+            RecallMode::Range => self.range_question(state, chunk),
+            RecallMode::DigitsAt => self.digits_at_question(state, chunk),
+            RecallMode::BlockStart => self.block_start_question(state),
+        };
+        self.input.clear();
+        self.hide_at = None;
+    }
+
+    /// Like [`Recall::next_round`], but when the range reaches past the
+    /// digits worked out so far they are computed on another thread first,
+    /// and the round is asked once they are ready.
+    fn next_round_in_background(&mut self, state: &GameState) {
+        let end = match Self::mode(state) {
+            RecallMode::Frontier => 0,
+            _ => self.range(state).1,
+        };
+        match self.sequence {
+            Sequence::Constant(constant) if self.digits.len() < end => {
+                let ready = Arc::new(OnceLock::new());
+                let slot = Arc::clone(&ready);
+                std::thread::spawn(move || {
+                    let _ = slot.set(constant.digits(end));
+                });
+                self.pending = Some(ready);
+            }
+            _ => self.next_round(state),
+        }
+    }
+
+    /// The chunk right after the frontier. A finished custom sequence is
+    /// asked back whole.
+    fn frontier_question(&mut self, state: &GameState, mut chunk: usize) -> Question {
+        self.position = self.frontier(state);
+        match self.sequence.len() {
+            Some(len) if self.position >= len => {
+                self.position = 0;
+                chunk = len;
+            }
+            Some(len) => chunk = chunk.min(len - self.position),
+            None => {}
+        }
+        self.chunk_question(state, chunk)
+    }
+
+    /// The next chunk of the practice range.
+    fn range_question(&mut self, state: &GameState, chunk: usize) -> Question {
+        let (from, to) = self.range(state);
+        let cursor = Self::counter(state, RANGE_CURSOR, 0) as usize;
+        self.position = if cursor + 1 < from || cursor >= to {
+            from - 1
+        } else {
+            cursor
+        };
+        self.chunk_question(state, chunk.min(to - self.position))
+    }
+
+    /// `chunk` digits from `self.position`, shown for the study time first.
+    fn chunk_question(&mut self, state: &GameState, chunk: usize) -> Question {
+        let label = self.sequence.label().to_string();
+        let end = self.position + chunk;
+        self.load(end);
+        let expected = self.text(self.position, chunk);
+
+        let prompt = if self.sequence.len() == Some(chunk) {
+            format!("Type all of {}", label)
+        } else if self.position == 0 {
+            format!("Type the first {} digits of {}", chunk, label)
+        } else {
+            format!(
+                "{} {} → type digits {}–{}",
+                label,
+                self.context(self.position),
+                self.position + 1,
+                end
+            )
+        };
+        let mut question = Question::new(prompt, QuestionKind::Digits, vec![expected.clone()])
+            .with_item(format!(
+                "{}:{}-{}",
+                self.sequence.id(),
+                self.position + 1,
+                end
+            ))
+            .with_explanation(format!(
+                "Digits {}–{} of {} are {}",
+                self.position + 1,
+                end,
+                label,
+                expected
+            ));
+        if !Self::study_time(state).is_zero() {
+            let mnemonic = self.pegs.mnemonic(&expected);
+            question = question.with_study(expected, Self::study_time(state));
+            if Self::counter(state, MNEMONICS, 0) == 1 {
+                question = question.with_study_note(mnemonic);
+            }
+        }
+        question
+    }
+
+    /// The digits starting at a random position of the range, asked cold.
+    fn digits_at_question(&mut self, state: &GameState, chunk: usize) -> Question {
+        let label = self.sequence.label().to_string();
+        let (from, to) = self.range(state);
+        self.load(to);
+        let length = chunk.min(to - from + 1);
+        self.position = self.rng.gen_range(from - 1..=to - length);
+        let end = self.position + length;
+        let expected = self.text(self.position, length);
+
+        let mut question = Question::new(
+            format!(
+                "What are the {} digits of {} starting at position {}?",
+                length,
+                label,
+                self.position + 1
+            ),
+            QuestionKind::Digits,
+            vec![expected.clone()],
+        )
+        .with_item(format!(
+            "{}:{}-{}",
+            self.sequence.id(),
+            self.position + 1,
+            end
+        ))
+        .with_explanation(format!(
+            "Digits {}–{} of {} are {}{}",
+            self.position + 1,
+            end,
+            label,
+            self.context(self.position),
+            expected
+        ));
+        if self.position > 0 {
+            question = question.with_hint(format!("It follows {}", self.context(self.position)));
+        }
+        question
+    }
+
+    /// A random block from the range, whose starting position is asked.
+    /// Every place the block starts within the range is accepted.
+    fn block_start_question(&mut self, state: &GameState) -> Question {
+        let label = self.sequence.label().to_string();
+        let (from, to) = self.range(state);
+        self.load(to);
+        let length = BLOCK_LENGTH.min(to - from + 1);
+        self.position = self.rng.gen_range(from - 1..=to - length);
+        let block = self.text(self.position, length);
+        let mut accepted = vec![(self.position + 1).to_string()];
+        accepted.extend(
+            (from - 1..=to - length)
+                .filter(|&start| start != self.position && self.text(start, length) == block)
+                .map(|start| (start + 1).to_string()),
+        );
+
+        let mut question = Question::new(
+            format!(
+                "At which position of {} does {} start? (somewhere in {}–{})",
+                label, block, from, to
+            ),
+            QuestionKind::Number,
+            accepted,
+        )
+        .with_item(format!("{}#{}", self.sequence.id(), block))
+        .with_explanation(format!(
+            "{} is digits {}–{} of {}",
+            block,
+            self.position + 1,
+            self.position + length,
+            label
+        ));
+        if self.position > 0 {
+            question = question.with_hint(format!("It follows {}", self.context(self.position)));
+        }
+        question
+    }
+
+    /// Grades the round, pushing the frontier or the range cursor and the
+    /// streak on success.
+    pub fn check_answer(&mut self, state: &mut GameState, answer: &Answer) -> Outcome {
+        state.attempts += 1;
+        let streak_key = self.key(STREAK);
+        let outcome = if self.question.accepts(&answer.text) {
+            let end = self.position + self.question.solution().len();
+            match self.mode {
+                RecallMode::Frontier if end > self.frontier(state) => {
+                    state.counters.insert(self.key(FRONTIER), end as u32);
+                }
+                RecallMode::Range => {
+                    let (from, to) = self.range(state);
+                    let next = if end >= to { from - 1 } else { end };
+                    state.counters.insert(RANGE_CURSOR.into(), next as u32);
+                }
+                _ => {}
+            }
+            let streak = Self::counter(state, &streak_key, 0) + 1;
+            state.counters.insert(streak_key, streak);
+            if streak > self.best_streak(state) {
+                state.counters.insert(self.key(BEST_STREAK), streak);
+            }
+            let points = match self.mode {
+                RecallMode::BlockStart => BLOCK_POINTS,
+                _ => self.question.solution().len() as i32,
+            };
+            Outcome::graded(Verdict::Correct, points, &self.question, answer)
+        } else {
+            state.counters.remove(&streak_key);
+            Outcome::graded(Verdict::Wrong, -1, &self.question, answer)
+        };
+        state.score += outcome.points;
+        outcome
+    }
+
+    /// Draws the study phase, then the answer box, with the training options below.
+    pub fn update(&mut self, ui: &mut egui::Ui, state: &mut GameState) -> Option<String> {
+        ui.label(format!(
+            "Frontier: {} digits · best streak {}",
+            self.frontier(state),
+            self.best_streak(state)
+        ));
+
+        if let Some(ready) = &self.pending {
+            let Some(digits) = ready.get() else {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(format!(
+                        "Working out the digits of {}…",
+                        self.sequence.label()
+                    ));
+                });
+                ui.ctx().request_repaint_after(Duration::from_millis(100));
+                return None;
+            };
+            if digits.len() > self.digits.len() {
+                self.digits = digits.chars().collect();
+            }
+            self.next_round(state);
+        }
+
+        let mut submitted = false;
+        let hide_at = self.question.study.as_ref().map(|study| {
+            *self
+                .hide_at
+                .get_or_insert_with(|| Instant::now() + study.duration)
+        });
+        let left = hide_at.map_or(Duration::ZERO, |at| {
+            at.saturating_duration_since(Instant::now())
+        });
+        if !left.is_zero() {
+            let study = self.question.study.clone().unwrap_or_default();
+            ui.label(format!("Memorise digits {}:", self.position + 1));
+            ui.label(egui::RichText::new(study.content).monospace().size(36.0));
+            if let Some(note) = study.note {
+                ui.label(egui::RichText::new(note).italics());
+            }
+            ui.label(format!("Hidden in {:.1}s", left.as_secs_f32()));
+            if ui.button("I'm ready").clicked() {
+                self.hide_at = Some(Instant::now());
+            }
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+        } else {
+            ui.label(&self.question.prompt);
+            let hint = match self.question.kind {
+                QuestionKind::Number => "Type the position, like 120",
+                _ => "Type the digits only, like 14159",
+            };
+            ui.add(egui::TextEdit::singleline(&mut self.input))
+                .on_hover_text(hint);
+            submitted = ui.button("Check").clicked();
+        }
+
+        ui.collapsing("Training", |ui| {
+            let mut mode = Self::mode(state);
+            egui::ComboBox::from_label("Drill")
+                .selected_text(mode.label())
+                .show_ui(ui, |ui| {
+                    for option in RecallMode::ALL {
+                        ui.selectable_value(&mut mode, option, option.label());
+                    }
+                });
+            let (mut from, mut to) = self.range_draft.unwrap_or_else(|| self.range(state));
+            let limit = self.range_limit();
+            let mut editing = false;
+            if mode != RecallMode::Frontier {
+                ui.horizontal(|ui| {
+                    ui.label("Digits");
+                    let start = ui.add(egui::DragValue::new(&mut from).clamp_range(1..=to));
+                    ui.label("to");
+                    let end = ui.add(egui::DragValue::new(&mut to).clamp_range(from..=limit));
+                    editing = [start, end]
+                        .iter()
+                        .any(|field| field.dragged() || field.has_focus());
+                });
+            }
+            let mut changed = false;
+            if editing {
+                self.range_draft = Some((from, to));
+            } else {
+                self.range_draft = None;
+                if (from, to) != self.range(state) {
+                    Self::set_range(state, from, to);
+                    changed = true;
+                }
+            }
+            if mode != Self::mode(state) {
+                Self::set_mode(state, mode);
+                changed = true;
+            }
+            if changed {
+                self.next_round_in_background(state);
+            }
+
+            let mut chunk = Self::counter(state, CHUNK, DEFAULT_CHUNK);
+            let mut seconds = Self::counter(state, STUDY_SECONDS, DEFAULT_STUDY_SECONDS);
+            ui.add(egui::Slider::new(&mut chunk, 1..=20).text("digits per round"));
+            ui.add(egui::Slider::new(&mut seconds, 0..=30).text("seconds to memorise"));
+            let mut mnemonics = Self::counter(state, MNEMONICS, 0) == 1;
+            ui.checkbox(
+                &mut mnemonics,
+                "Show Major System peg words while memorising",
+            );
+            ui.label("Changes apply from the next round.");
+            if mnemonics != (Self::counter(state, MNEMONICS, 0) == 1) {
+                state
+                    .counters
+                    .insert(MNEMONICS.into(), u32::from(mnemonics));
+            }
+            if chunk != Self::counter(state, CHUNK, DEFAULT_CHUNK) {
+                state.counters.insert(CHUNK.into(), chunk);
+            }
+            if seconds != Self::counter(state, STUDY_SECONDS, DEFAULT_STUDY_SECONDS) {
+                state.counters.insert(STUDY_SECONDS.into(), seconds);
+            }
+        });
+
+        submitted.then(|| std::mem::take(&mut self.input))
+    }
+}
@@ -0,0 +1,227 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// How an answer to a [`Question`] is expected to be typed and compared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuestionKind {
+    /// A word or phrase, compared case-insensitively.
+    #[default]
+    Text,
+    /// An integer, compared by value.
+    Number,
+    /// A run of digits, compared character by character.
+    Digits,
+}
+
+/// Material shown on its own before the prompt and hidden once `duration`
+/// has passed, for rounds that test memorisation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Study {
+    pub content: String,
+    pub duration: Duration,
+    /// Shown next to the content to help remember it, such as mnemonic words.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// One round of a kata as presented to the player.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Question {
+    pub prompt: String,
+    pub kind: QuestionKind,
+    /// Stable id of the item being asked, for games that draw from a fixed set.
+    pub item: Option<String>,
+    /// Answers graded as correct; the first one is shown as the solution.
+    pub accepted: Vec<String>,
+    pub hints: Vec<String>,
+    /// Shown after grading to explain the solution.
+    pub explanation: Option<String>,
+    /// Memorised before the prompt is asked; `None` for ordinary rounds.
+    #[serde(default)]
+    pub study: Option<Study>,
+}
+
+impl Question {
+    pub fn new(prompt: impl Into<String>, kind: QuestionKind, accepted: Vec<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            kind,
+            item: None,
+            accepted,
+            hints: Vec::new(),
+            explanation: None,
+            study: None,
+        }
+    }
+
+    pub fn with_item(mut self, item: impl Into<String>) -> Self {
+        self.item = Some(item.into());
+        self
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hints.push(hint.into());
+        self
+    }
+
+    pub fn with_explanation(mut self, explanation: impl Into<String>) -> Self {
+        self.explanation = Some(explanation.into());
+        self
+    }
+
+    pub fn with_study(mut self, content: impl Into<String>, duration: Duration) -> Self {
+        self.study = Some(Study {
+            content: content.into(),
+            duration,
+            note: None,
+        });
+        self
+    }
+
+    /// Adds a note to the study material set by [`Question::with_study`].
+    pub fn with_study_note(mut self, note: impl Into<String>) -> Self {
+        if let Some(study) = &mut self.study {
+            study.note = Some(note.into());
+        }
+        self
+    }
+
+    /// The canonical solution, or an empty string when there is none.
+    pub fn solution(&self) -> &str {
+        self.accepted
+            .first()
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Whether `text` matches one of the accepted answers for this kind.
+    pub fn accepts(&self, text: &str) -> bool {
+        let text = text.trim();
+        self.accepted.iter().any(|accepted| match self.kind {
+            QuestionKind::Text => text.eq_ignore_ascii_case(accepted),
+            QuestionKind::Number => match (text.parse::<i64>(), accepted.parse::<i64>()) {
+                (Ok(given), Ok(expected)) => given == expected,
+                _ => false,
+            },
+            QuestionKind::Digits => text == accepted,
+        })
+    }
+}
+
+/// What the player submitted and how long they took.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Answer {
+    pub text: String,
+    pub elapsed: Duration,
+}
+
+impl Answer {
+    pub fn new(text: impl Into<String>, elapsed: Duration) -> Self {
+        Self {
+            text: text.into(),
+            elapsed,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Correct,
+    Partial,
+    Wrong,
+}
+
+impl Verdict {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Verdict::Correct => "✓",
+            Verdict::Partial => "≈",
+            Verdict::Wrong => "✗",
+        }
+    }
+}
+
+/// One position of a typed digit run next to the digit expected there.
+/// Either side is `None` where the answer was shorter or longer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mark {
+    pub given: Option<char>,
+    pub expected: Option<char>,
+}
+
+impl Mark {
+    pub fn is_correct(self) -> bool {
+        self.given == self.expected
+    }
+
+    /// The typed digit, or `_` where one is missing.
+    pub fn shown(self) -> char {
+        self.given.unwrap_or('_')
+    }
+
+    /// Compares `given` with `expected` position by position.
+    pub fn compare(given: &str, expected: &str) -> Vec<Mark> {
+        let (mut given, mut expected) = (given.trim().chars(), expected.chars());
+        std::iter::from_fn(|| match (given.next(), expected.next()) {
+            (None, None) => None,
+            (given, expected) => Some(Mark { given, expected }),
+        })
+        .collect()
+    }
+}
+
+/// The graded result of a single round.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Outcome {
+    pub verdict: Verdict,
+    pub points: i32,
+    pub correct_answer: String,
+    pub elapsed: Duration,
+    pub explanation: Option<String>,
+    /// The answer digit by digit, for wrong [`QuestionKind::Digits`] rounds.
+    #[serde(default)]
+    pub marks: Vec<Mark>,
+}
+
+impl Outcome {
+    pub fn new(
+        verdict: Verdict,
+        points: i32,
+        correct_answer: impl Into<String>,
+        answer: &Answer,
+    ) -> Self {
+        Self {
+            verdict,
+            points,
+            correct_answer: correct_answer.into(),
+            elapsed: answer.elapsed,
+            explanation: None,
+            marks: Vec::new(),
+        }
+    }
+
+    /// Grades against `question`, carrying over its solution and explanation.
+    /// Wrong digit runs are also marked digit by digit.
+    pub fn graded(verdict: Verdict, points: i32, question: &Question, answer: &Answer) -> Self {
+        let marks = if verdict != Verdict::Correct && question.kind == QuestionKind::Digits {
+            Mark::compare(&answer.text, question.solution())
+        } else {
+            Vec::new()
+        };
+        Self {
+            explanation: question.explanation.clone(),
+            marks,
+            ..Self::new(verdict, points, question.solution(), answer)
+        }
+    }
+
+    pub fn with_explanation(mut self, explanation: impl Into<String>) -> Self {
+        self.explanation = Some(explanation.into());
+        self
+    }
+
+    pub fn is_correct(&self) -> bool {
+        self.verdict == Verdict::Correct
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::Instant;
+
+use crate::cli::PlayOptions;
+use crate::games::{self, Answer, Outcome, Verdict};
+use crate::scheduler::{self, Clock};
+use crate::storage::{AttemptRecord, StorageBackend, StorageError};
+
+/// Typed instead of an answer to show the hints of the current round.
+const HINT_COMMAND: &str = "hint";
+/// Typed instead of an answer to stop before the last round.
+const QUIT_COMMAND: &str = "quit";
+
+#[derive(Debug)]
+pub enum PlayError {
+    UnknownGame {
+        name: String,
+        known: Vec<&'static str>,
+    },
+    Io(io::Error),
+    Storage(StorageError),
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayError::UnknownGame { name, known } => {
+                write!(f, "no game '{}'; choose one of: {}", name, known.join(", "))
+            }
+            PlayError::Io(err) => err.fmt(f),
+            PlayError::Storage(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PlayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PlayError::UnknownGame { .. } => None,
+            PlayError::Io(err) => Some(err),
+            PlayError::Storage(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for PlayError {
+    fn from(err: io::Error) -> Self {
+        PlayError::Io(err)
+    }
+}
+
+impl From<StorageError> for PlayError {
+    fn from(err: StorageError) -> Self {
+        PlayError::Storage(err)
+    }
+}
+
+/// What happened during one terminal session.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlaySummary {
+    pub rounds: usize,
+    pub correct: usize,
+    pub points: i32,
+}
+
+/// Plays `options.game` through `input` and `output`, saving after every
+/// answer exactly like the window does. Stops after `options.rounds`, at the
+/// end of input, or when the player types `quit`. With a `seed` the clock of
+/// the review schedule is stopped at the start, so the rounds are the same on
+/// every run from the same progress given the same answers. Only when `output` is
+/// a `terminal` is study material erased from the screen once hidden.
+pub fn play(
+    storage: &mut dyn StorageBackend,
+    options: &PlayOptions,
+    seed: Option<u64>,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+    terminal: bool,
+) -> Result<PlaySummary, PlayError> {
+    let mut modules = games::all();
+    let Some(index) = modules.iter().position(|game| game.id() == options.game) else {
+        return Err(PlayError::UnknownGame {
+            name: options.game.clone(),
+            known: modules.iter().map(|game| game.id()).collect(),
+        });
+    };
+    let game = &mut modules[index];
+
+    let mut data = storage.load()?;
+    game.restore(data.games.get(game.id()).cloned().unwrap_or_default());
+    game.share_progress(&data.games);
+    if let Some(seed) = seed {
+        // Missed items come back after a delay, so stop the clock as well
+        // or the rounds would depend on how fast the player answers.
+        game.set_clock(Clock::Fixed(scheduler::now()));
+        game.reseed(seed);
+    }
+
+    writeln!(output, "{} — {}", game.name(), game.description())?;
+    writeln!(
+        output,
+        "Type your answer and press Enter; '{}' shows a hint, '{}' stops.",
+        HINT_COMMAND, QUIT_COMMAND
+    )?;
+
+    let mut summary = PlaySummary::default();
+    'rounds: for round in 1..=options.rounds {
+        writeln!(output)?;
+        if let Some(study) = &game.question().study {
+            let note = study
+                .note
+                .as_ref()
+                .map(|note| format!("  ({})", note))
+                .unwrap_or_default();
+            writeln!(
+                output,
+                "Memorise ({:.1}s): {}{}",
+                study.duration.as_secs_f32(),
+                study.content,
+                note
+            )?;
+            output.flush()?;
+            thread::sleep(study.duration);
+            if terminal {
+                // Move up over the study line and blank it.
+                write!(output, "\x1b[1A\x1b[2K")?;
+            }
+            writeln!(output, "(hidden)")?;
+        }
+        writeln!(
+            output,
+            "[{}/{}] {}",
+            round,
+            options.rounds,
+            game.question().prompt
+        )?;
+        let started = Instant::now();
+        let text = loop {
+            write!(output, "> ")?;
+            output.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                break 'rounds;
+            }
+            match line.trim() {
+                "" => {}
+                QUIT_COMMAND => break 'rounds,
+                HINT_COMMAND if game.question().hints.is_empty() => {
+                    writeln!(output, "No hint for this round.")?;
+                }
+                HINT_COMMAND => {
+                    for hint in &game.question().hints {
+                        writeln!(output, "Hint: {}", hint)?;
+                    }
+                }
+                text => break text.to_string(),
+            }
+        };
+
+        let answer = Answer::new(text, started.elapsed());
+        let outcome = game.check_answer(&answer);
+        print_feedback(output, &outcome)?;
+
+        summary.rounds += 1;
+        summary.points += outcome.points;
+        if outcome.verdict == Verdict::Correct {
+            summary.correct += 1;
+        }
+
+        let record = AttemptRecord::new(
+            game.id(),
+            game.question(),
+            &answer,
+            &outcome,
+            scheduler::now(),
+        );
+        storage.record_attempt(&mut data, record)?;
+        data.games
+            .insert(game.id().to_string(), game.state().clone());
+        storage.save(&data)?;
+        game.next_round();
+    }
+
+    writeln!(output)?;
+    writeln!(
+        output,
+        "{} of {} correct, {:+} points · Score: {} | Attempts: {}",
+        summary.correct,
+        summary.rounds,
+        summary.points,
+        game.state().score,
+        game.state().attempts
+    )?;
+    Ok(summary)
+}
+
+/// Text version of the window's feedback panel.
+fn print_feedback(output: &mut dyn Write, outcome: &Outcome) -> io::Result<()> {
+    writeln!(
+        output,
+        "{} {:+} points · Answer: {} · {:.1}s",
+        outcome.verdict.symbol(),
+        outcome.points,
+        outcome.correct_answer,
+        outcome.elapsed.as_secs_f32()
+    )?;
+    if !outcome.marks.is_empty() {
+        // Carets under the digits that differ, as there is no colour here.
+        let typed: String = outcome.marks.iter().map(|mark| mark.shown()).collect();
+        let carets: String = outcome
+            .marks
+            .iter()
+            .map(|mark| if mark.is_correct() { ' ' } else { '^' })
+            .collect();
+        writeln!(output, "  Yours:  {}", typed)?;
+        writeln!(output, "          {}", carets.trim_end())?;
+    }
+    if let Some(explanation) = &outcome.explanation {
+        writeln!(output, "  {}", explanation)?;
+    }
+    Ok(())
+}
@@ -1,3 +1,11 @@
+pub mod cli;
+pub mod daily;
+pub mod digits;
 pub mod games;
+pub mod headless;
+pub mod scheduler;
+pub mod stats;
 pub mod storage;
+#[cfg(feature = "tui")]
+pub mod tui;
 pub mod ui;
@@ -1,16 +1,77 @@
-mod games;
-mod storage;
-mod ui;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::process;
 
-use std::path::PathBuf;
+use memorykata::cli::{self, Command};
+use memorykata::stats::Report;
+use memorykata::{digits, headless, scheduler, ui};
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 fn main() -> Result<()> {
-    let storage_path = dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".memorykata")
-        .join("save.json");
+    let cli = match cli::parse(env::args_os().skip(1)) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("memorykata: {}", err);
+            process::exit(2);
+        }
+    };
+    if cli.command == Command::Help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+
+    // Digits of π and other constants are shared by every profile.
+    digits::set_cache_dir(cli.options.data_dir().join("cache"));
+    let profiles = cli.options.profiles()?;
+    let mut storage = profiles.open_storage(&profiles.current().id)?;
+    let seed = cli.options.seed;
+
+    match &cli.command {
+        Command::Play(options) => {
+            let mut input = io::stdin().lock();
+            let mut output = io::stdout().lock();
+            let terminal = output.is_terminal();
+            if let Err(err) = headless::play(
+                storage.as_mut(),
+                options,
+                seed,
+                &mut input,
+                &mut output,
+                terminal,
+            ) {
+                eprintln!("memorykata: {}", err);
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Stats(format) => {
+            let data = storage.load()?;
+            let report = Report::collect(
+                &profiles.current().name,
+                storage.as_ref(),
+                &data,
+                scheduler::now(),
+            )?;
+            match report.write(*format, &mut io::stdout().lock()) {
+                // Piping into `head` and the like is fine.
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+                result => result?,
+            }
+            return Ok(());
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui => {
+            memorykata::tui::run(storage, seed)?;
+            return Ok(());
+        }
+        #[cfg(not(feature = "tui"))]
+        Command::Tui => {
+            eprintln!("memorykata: this build does not include the terminal UI (feature `tui`)");
+            process::exit(2);
+        }
+        Command::Window | Command::Help => {}
+    }
 
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([900.0, 600.0]),
@@ -20,10 +81,7 @@ fn main() -> Result<()> {
     eframe::run_native(
         "MemoryKata",
         native_options,
-        Box::new(move |_cc| {
-            let storage = storage::Storage::new(storage_path.clone());
-            Box::new(ui::App::new(storage))
-        }),
+        Box::new(move |_cc| Box::new(ui::App::new(profiles, storage, seed))),
     )?;
 
     Ok(())
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::games::{Outcome, Question, Verdict};
+
+const DAY: u64 = 24 * 60 * 60;
+/// How long a failed item waits before it is asked again.
+const RELEARN_DELAY: u64 = 60;
+const MIN_EASE: f32 = 1.3;
+
+/// Seconds since the Unix epoch, the clock used for every due date.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Where a game reads the time its review schedule is kept against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Clock {
+    /// The system clock, see [`now`].
+    #[default]
+    System,
+    /// Stopped at a Unix timestamp, so that a seeded session asks the same
+    /// rounds however long its answers take.
+    Fixed(u64),
+}
+
+impl Clock {
+    pub fn now(self) -> u64 {
+        match self {
+            Clock::System => now(),
+            Clock::Fixed(at) => at,
+        }
+    }
+}
+
+/// SM-2 review state of a single item.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReviewCard {
+    pub ease: f32,
+    pub interval_days: u32,
+    pub repetitions: u32,
+    /// Unix timestamp after which the item should be asked again.
+    pub due: u64,
+}
+
+impl Default for ReviewCard {
+    fn default() -> Self {
+        Self {
+            ease: 2.5,
+            interval_days: 0,
+            repetitions: 0,
+            due: 0,
+        }
+    }
+}
+
+impl ReviewCard {
+    /// Applies an SM-2 review graded from 0 (blackout) to 5 (perfect recall).
+    pub fn review(&mut self, quality: u8, now: u64) {
+        let quality = quality.min(5);
+        if quality >= 3 {
+            self.repetitions += 1;
+            self.interval_days = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (self.interval_days as f32 * self.ease).round() as u32,
+            };
+            self.due = now + self.interval_days as u64 * DAY;
+        } else {
+            self.repetitions = 0;
+            self.interval_days = 0;
+            self.due = now + RELEARN_DELAY;
+        }
+
+        let lapse = (5 - quality) as f32;
+        self.ease = (self.ease + 0.1 - lapse * (0.08 + lapse * 0.02)).max(MIN_EASE);
+    }
+}
+
+/// Per-game spaced-repetition schedule keyed by item id.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scheduler {
+    cards: BTreeMap<String, ReviewCard>,
+}
+
+impl Scheduler {
+    pub fn card(&self, item: &str) -> Option<&ReviewCard> {
+        self.cards.get(item)
+    }
+
+    pub fn review(&mut self, item: &str, quality: u8, now: u64) {
+        self.cards
+            .entry(item.to_string())
+            .or_default()
+            .review(quality, now);
+    }
+
+    /// Reviews the item behind `question` according to how it was answered.
+    pub fn record(&mut self, question: &Question, outcome: &Outcome, now: u64) {
+        if let Some(item) = &question.item {
+            self.review(item, Self::quality(outcome), now);
+        }
+    }
+
+    /// Maps a graded round onto the SM-2 quality scale.
+    pub fn quality(outcome: &Outcome) -> u8 {
+        match outcome.verdict {
+            Verdict::Correct if outcome.elapsed.as_secs() < 5 => 5,
+            Verdict::Correct => 4,
+            Verdict::Partial => 3,
+            Verdict::Wrong => 1,
+        }
+    }
+
+    /// Chooses the index of the next item to ask.
+    ///
+    /// The most overdue item wins; when nothing is due a never-seen item is
+    /// picked at random, and failing that the item due soonest.
+    pub fn pick<'a, R: Rng + ?Sized>(
+        &self,
+        items: impl IntoIterator<Item = &'a str>,
+        now: u64,
+        rng: &mut R,
+    ) -> Option<usize> {
+        let items: Vec<&str> = items.into_iter().collect();
+        let due = |index: &usize| self.cards.get(items[*index]).map(|card| card.due);
+
+        let overdue = (0..items.len())
+            .filter(|index| due(index).is_some_and(|due| due <= now))
+            .min_by_key(|index| due(index));
+        if overdue.is_some() {
+            return overdue;
+        }
+
+        let unseen = (0..items.len())
+            .filter(|index| due(index).is_none())
+            .choose(rng);
+        if unseen.is_some() {
+            return unseen;
+        }
+
+        (0..items.len()).min_by_key(|index| due(index))
+    }
+
+    /// Folds in another schedule, keeping whichever card of an item has been
+    /// reviewed further along.
+    pub fn merge(&mut self, other: Scheduler) {
+        for (item, card) in other.cards {
+            match self.cards.get(&item) {
+                Some(existing) if existing.due >= card.due => {}
+                _ => {
+                    self.cards.insert(item, card);
+                }
+            }
+        }
+    }
+
+    /// Number of known items whose review is due at `now`.
+    pub fn due_count(&self, now: u64) -> usize {
+        self.cards.values().filter(|card| card.due <= now).count()
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::games::{self, Verdict};
+use crate::storage::{HistoryQuery, SaveData, StorageBackend, StorageError};
+
+/// Output of `memorykata stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format '{}'; use table, json or csv", text)),
+        }
+    }
+}
+
+/// Progress of one game as reported by `memorykata stats`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GameStats {
+    /// [`GameEngine::id`](crate::games::GameEngine::id), or the saved key of a
+    /// game this build does not know.
+    pub game: String,
+    pub name: String,
+    pub score: i32,
+    pub attempts: i32,
+    /// Share of recorded answers that were correct; `None` without history.
+    pub accuracy: Option<f64>,
+    /// Correct answers in a row up to the latest one.
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// Review items whose due date has passed.
+    pub due: usize,
+}
+
+/// Everything `memorykata stats` prints for one profile.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub profile: String,
+    /// Unix timestamp the figures were taken at.
+    pub generated_at: u64,
+    pub games: Vec<GameStats>,
+}
+
+impl Report {
+    /// Gathers the figures of every game, in main menu order, followed by
+    /// any saved games this build does not know.
+    pub fn collect(
+        profile: &str,
+        storage: &dyn StorageBackend,
+        data: &SaveData,
+        now: u64,
+    ) -> Result<Self, StorageError> {
+        let known: Vec<(String, String)> = games::all()
+            .iter()
+            .map(|game| (game.id().to_string(), game.name().to_string()))
+            .collect();
+        let unknown = data
+            .games
+            .keys()
+            .filter(|id| !known.iter().any(|(known, _)| known == *id))
+            .map(|id| (id.clone(), id.clone()));
+
+        let mut games = Vec::new();
+        for (id, name) in known.iter().cloned().chain(unknown) {
+            let state = data.games.get(&id).cloned().unwrap_or_default();
+            let attempts = storage.stats(data, &id)?;
+            let query = HistoryQuery {
+                game: Some(id.clone()),
+                ..Default::default()
+            };
+            let (current_streak, best_streak) = streaks(
+                storage
+                    .history(data, &query)?
+                    .iter()
+                    .rev()
+                    .map(|record| record.verdict),
+            );
+            games.push(GameStats {
+                game: id,
+                name,
+                score: state.score,
+                attempts: state.attempts,
+                accuracy: (attempts.attempts > 0).then(|| attempts.accuracy()),
+                current_streak,
+                best_streak,
+                due: state.schedule.due_count(now),
+            });
+        }
+
+        Ok(Self {
+            profile: profile.to_string(),
+            generated_at: now,
+            games,
+        })
+    }
+
+    pub fn write(&self, format: Format, output: &mut dyn Write) -> io::Result<()> {
+        match format {
+            Format::Table => self.write_table(output),
+            Format::Json => {
+                serde_json::to_writer_pretty(&mut *output, self)?;
+                writeln!(output)
+            }
+            Format::Csv => self.write_csv(output),
+        }
+    }
+
+    fn write_table(&self, output: &mut dyn Write) -> io::Result<()> {
+        let width = self
+            .games
+            .iter()
+            .map(|game| game.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Game".len());
+        writeln!(output, "Profile: {}", self.profile)?;
+        writeln!(
+            output,
+            "{:<width$}  {:>6}  {:>8}  {:>8}  {:>6}  {:>4}  {:>3}",
+            "Game", "Score", "Attempts", "Accuracy", "Streak", "Best", "Due"
+        )?;
+        for game in &self.games {
+            let accuracy = game.accuracy.map_or("-".to_string(), |accuracy| {
+                format!("{:.1}%", accuracy * 100.0)
+            });
+            writeln!(
+                output,
+                "{:<width$}  {:>6}  {:>8}  {:>8}  {:>6}  {:>4}  {:>3}",
+                game.name,
+                game.score,
+                game.attempts,
+                accuracy,
+                game.current_streak,
+                game.best_streak,
+                game.due
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_csv(&self, output: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            output,
+            "profile,game,name,score,attempts,accuracy,current_streak,best_streak,due"
+        )?;
+        for game in &self.games {
+            writeln!(
+                output,
+                "{},{},{},{},{},{},{},{},{}",
+                CsvField(&self.profile),
+                CsvField(&game.game),
+                CsvField(&game.name),
+                game.score,
+                game.attempts,
+                game.accuracy
+                    .map_or(String::new(), |accuracy| format!("{:.4}", accuracy)),
+                game.current_streak,
+                game.best_streak,
+                game.due
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Current and best run of correct answers in `verdicts`, oldest first.
+pub fn streaks(verdicts: impl IntoIterator<Item = Verdict>) -> (u32, u32) {
+    let mut current = 0;
+    let mut best = 0;
+    for verdict in verdicts {
+        if verdict == Verdict::Correct {
+            current += 1;
+            best = best.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    (current, best)
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+struct CsvField<'a>(&'a str);
+
+impl fmt::Display for CsvField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.contains([',', '"', '\n', '\r']) {
+            write!(f, "\"{}\"", self.0.replace('"', "\"\""))
+        } else {
+            f.write_str(self.0)
+        }
+    }
+}
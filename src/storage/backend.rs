@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{AttemptRecord, AttemptStats, HistoryQuery, SaveData, Storage, StorageError};
+
+/// Most answers the JSON [`Storage`] keeps for each game, as the whole file
+/// is rewritten after every answer. The oldest go first and are counted in
+/// [`SaveData::trimmed_history`]; SQLite keeps them all.
+pub const JSON_HISTORY_PER_GAME: usize = 1000;
+
+/// Where progress lives. The JSON [`Storage`] keeps every answer inside the
+/// save data; the SQLite backend keeps them in a table so history and
+/// statistics can be queried without loading everything into memory.
+pub trait StorageBackend {
+    fn load(&self) -> Result<SaveData, StorageError>;
+    fn save(&self, data: &SaveData) -> Result<(), StorageError>;
+    /// Records one answered question. `data` is the progress currently in
+    /// memory, for backends that keep history inside the save data.
+    fn record_attempt(
+        &mut self,
+        data: &mut SaveData,
+        record: AttemptRecord,
+    ) -> Result<(), StorageError>;
+    fn history(
+        &self,
+        data: &SaveData,
+        query: &HistoryQuery,
+    ) -> Result<Vec<AttemptRecord>, StorageError>;
+    fn stats(&self, data: &SaveData, game: &str) -> Result<AttemptStats, StorageError>;
+    /// Forgets every recorded answer, e.g. before importing a replacement.
+    fn clear_history(&mut self, data: &mut SaveData) -> Result<(), StorageError>;
+    /// Swaps every recorded answer for `records`, oldest first, as when
+    /// importing a replacement. Backends that can should do this atomically.
+    fn replace_history(
+        &mut self,
+        data: &mut SaveData,
+        records: Vec<AttemptRecord>,
+    ) -> Result<(), StorageError> {
+        self.clear_history(data)?;
+        for record in records {
+            self.record_attempt(data, record)?;
+        }
+        Ok(())
+    }
+}
+
+impl StorageBackend for Storage {
+    fn load(&self) -> Result<SaveData, StorageError> {
+        Storage::load(self)
+    }
+
+    fn save(&self, data: &SaveData) -> Result<(), StorageError> {
+        Storage::save(self, data)
+    }
+
+    /// Adds to [`SaveData::history`], dropping the game's oldest answer once
+    /// it has more than [`JSON_HISTORY_PER_GAME`]. Written with the next save.
+    fn record_attempt(
+        &mut self,
+        data: &mut SaveData,
+        record: AttemptRecord,
+    ) -> Result<(), StorageError> {
+        data.history.push(record);
+        trim_history(data);
+        Ok(())
+    }
+
+    fn history(
+        &self,
+        data: &SaveData,
+        query: &HistoryQuery,
+    ) -> Result<Vec<AttemptRecord>, StorageError> {
+        Ok(query.apply(&data.history))
+    }
+
+    fn stats(&self, data: &SaveData, game: &str) -> Result<AttemptStats, StorageError> {
+        let records = data.history.iter().filter(|record| record.game == game);
+        Ok(AttemptStats::from_records(records))
+    }
+
+    fn clear_history(&mut self, data: &mut SaveData) -> Result<(), StorageError> {
+        data.history.clear();
+        Ok(())
+    }
+
+    /// Trims once at the end rather than after every record.
+    fn replace_history(
+        &mut self,
+        data: &mut SaveData,
+        records: Vec<AttemptRecord>,
+    ) -> Result<(), StorageError> {
+        data.history = records;
+        trim_history(data);
+        Ok(())
+    }
+}
+
+/// Drops the oldest answers of every game beyond [`JSON_HISTORY_PER_GAME`]
+/// from [`SaveData::history`], counting them in [`SaveData::trimmed_history`].
+pub(crate) fn trim_history(data: &mut SaveData) {
+    let mut per_game: BTreeMap<&str, usize> = BTreeMap::new();
+    let newest_first: Vec<bool> = data
+        .history
+        .iter()
+        .rev()
+        .map(|record| {
+            let count = per_game.entry(&record.game).or_default();
+            *count += 1;
+            *count <= JSON_HISTORY_PER_GAME
+        })
+        .collect();
+    let mut keep = newest_first.into_iter().rev();
+    let trimmed = &mut data.trimmed_history;
+    data.history.retain(|record| {
+        let kept = keep.next().unwrap_or(true);
+        if !kept {
+            *trimmed.entry(record.game.clone()).or_default() += 1;
+        }
+        kept
+    });
+}
+
+/// Opens the backend matching the file extension: `.db` or `.sqlite` use
+/// SQLite when it is compiled in, anything else the JSON file.
+pub fn open(path: &Path) -> Result<Box<dyn StorageBackend>, StorageError> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "sqlite")]
+        Some("db" | "sqlite") => Ok(Box::new(super::SqliteStorage::open(path)?)),
+        _ => Ok(Box::new(Storage::new(path.to_path_buf()))),
+    }
+}
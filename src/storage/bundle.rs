@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::backend;
+use super::migrate::{self, MigrationError};
+use super::{HistoryQuery, SaveData, StorageBackend, StorageError};
+use crate::games::{GameState, PEAK_COUNTERS};
+
+/// Marker that tells a progress bundle apart from any other JSON file.
+const BUNDLE_FORMAT: &str = "memorykata-bundle";
+
+/// Portable snapshot of all progress, settings and history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub format: String,
+    /// Unix timestamp of the export.
+    pub exported_at: u64,
+    /// Full save data, with `history` filled in whatever the backend.
+    pub data: SaveData,
+}
+
+/// Summary shown before an import is confirmed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BundlePreview {
+    pub exported_at: u64,
+    pub games: usize,
+    pub total_score: i64,
+    pub total_attempts: i64,
+    pub history_entries: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Drop current progress and history in favour of the bundle.
+    Replace,
+    /// Keep current progress and fold the bundle into it.
+    Merge,
+}
+
+impl Bundle {
+    /// Snapshots `data`, pulling the full history out of `storage`.
+    pub fn export(
+        storage: &dyn StorageBackend,
+        data: &SaveData,
+        exported_at: u64,
+    ) -> Result<Self, StorageError> {
+        let mut history = storage.history(data, &HistoryQuery::default())?;
+        history.reverse();
+
+        Ok(Self {
+            format: BUNDLE_FORMAT.into(),
+            exported_at,
+            data: SaveData {
+                history,
+                ..data.clone()
+            },
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), StorageError> {
+        let json = serde_json::to_string_pretty(self).map_err(StorageError::Serialize)?;
+        fs::write(path, json).map_err(StorageError::io(path))
+    }
+
+    /// Reads a bundle, upgrading the progress inside it to the current schema.
+    pub fn read(path: &Path) -> Result<Self, StorageError> {
+        let content = fs::read_to_string(path).map_err(StorageError::io(path))?;
+        let mut value: Value = serde_json::from_str(&content).map_err(MigrationError::Parse)?;
+        if value.get("format").and_then(Value::as_str) != Some(BUNDLE_FORMAT) {
+            return Err(StorageError::NotABundle(path.to_path_buf()));
+        }
+
+        let data = value.get_mut("data").map(Value::take).unwrap_or_default();
+        value["data"] = migrate::upgrade(data)?;
+        Ok(serde_json::from_value(value).map_err(MigrationError::Schema)?)
+    }
+
+    pub fn preview(&self) -> BundlePreview {
+        let games = self.data.games.values();
+        BundlePreview {
+            exported_at: self.exported_at,
+            games: self.data.games.len(),
+            total_score: games.clone().map(|state| state.score as i64).sum(),
+            total_attempts: games.map(|state| state.attempts as i64).sum(),
+            history_entries: self.data.history.len(),
+        }
+    }
+
+    /// Applies the bundle to `data` and the history held by `storage`.
+    pub fn import(
+        self,
+        storage: &mut dyn StorageBackend,
+        data: &mut SaveData,
+        mode: ImportMode,
+    ) -> Result<(), StorageError> {
+        let mut incoming = self.data;
+        let history = std::mem::take(&mut incoming.history);
+
+        match mode {
+            ImportMode::Replace => {
+                // Progress is only swapped once the history has been.
+                storage.replace_history(&mut incoming, history)?;
+                *data = incoming;
+            }
+            ImportMode::Merge => {
+                let existing = storage.history(data, &HistoryQuery::default())?;
+                merge(data, incoming);
+                for record in history {
+                    if !existing.contains(&record) {
+                        storage.record_attempt(data, record)?;
+                    }
+                }
+            }
+        }
+        // Keep an in-memory log in answer order after interleaving two sources.
+        data.history.sort_by_key(|record| record.answered_at);
+        backend::trim_history(data);
+        Ok(())
+    }
+}
+
+/// Folds `incoming` into `data`: for each game the side with more attempts
+/// wins, keeping the other side's entries it lacks (custom sequences, peg
+/// words, sounds) and the higher frontier and best streak of every sequence.
+/// Review schedules are merged item by item, the daily challenge record with
+/// more days played wins, and settings stay as they are.
+fn merge(data: &mut SaveData, incoming: SaveData) {
+    if incoming.daily.results.len() > data.daily.results.len() {
+        data.daily = incoming.daily;
+    }
+    // Both sides may have dropped the same answers, so count them once.
+    for (id, count) in incoming.trimmed_history {
+        let trimmed = data.trimmed_history.entry(id).or_insert(0);
+        *trimmed = (*trimmed).max(count);
+    }
+    for (id, state) in incoming.games {
+        match data.games.get_mut(&id) {
+            Some(current) => {
+                let mut state = state;
+                let incoming_schedule = std::mem::take(&mut state.schedule);
+                let other = if state.attempts > current.attempts {
+                    let schedule = std::mem::take(&mut current.schedule);
+                    std::mem::replace(current, GameState { schedule, ..state })
+                } else {
+                    state
+                };
+                for (key, value) in other.entries {
+                    current.entries.entry(key).or_insert(value);
+                }
+                for (key, value) in other.counters {
+                    if is_peak_counter(&key) {
+                        let peak = current.counters.entry(key).or_insert(0);
+                        *peak = (*peak).max(value);
+                    }
+                }
+                current.schedule.merge(incoming_schedule);
+            }
+            None => {
+                data.games.insert(id, state);
+            }
+        }
+    }
+}
+
+/// Whether `key` is one of [`PEAK_COUNTERS`], either plain or followed by
+/// a sequence id.
+fn is_peak_counter(key: &str) -> bool {
+    PEAK_COUNTERS.iter().any(|name| {
+        key.strip_prefix(name)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use super::MigrationError;
+
+/// Everything that can go wrong while reading or writing progress.
+#[derive(Debug)]
+pub enum StorageError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Serialize(serde_json::Error),
+    Migration(MigrationError),
+    /// An import was pointed at a file that is not a progress bundle.
+    NotABundle(PathBuf),
+    /// `profiles.json` exists but cannot be understood.
+    CorruptProfiles(PathBuf),
+    /// A profile name that is empty or already taken.
+    InvalidProfileName(String),
+    UnknownProfile(String),
+    /// The profile being played cannot be deleted.
+    ProfileInUse(String),
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+}
+
+impl StorageError {
+    pub(crate) fn io(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| StorageError::Io { path, source }
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            StorageError::Serialize(err) => write!(f, "could not encode progress: {}", err),
+            StorageError::Migration(err) => err.fmt(f),
+            StorageError::NotABundle(path) => {
+                write!(f, "{} is not a MemoryKata progress bundle", path.display())
+            }
+            StorageError::CorruptProfiles(path) => {
+                write!(f, "{} is not a valid profile list", path.display())
+            }
+            StorageError::InvalidProfileName(name) if name.is_empty() => {
+                write!(f, "a profile needs a name")
+            }
+            StorageError::InvalidProfileName(name) => {
+                write!(f, "a profile called “{}” already exists", name)
+            }
+            StorageError::UnknownProfile(id) => write!(f, "no profile “{}”", id),
+            StorageError::ProfileInUse(id) => {
+                write!(f, "profile “{}” is in use; switch to another one first", id)
+            }
+            #[cfg(feature = "sqlite")]
+            StorageError::Sqlite(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Io { source, .. } => Some(source),
+            StorageError::Serialize(err) => Some(err),
+            StorageError::Migration(err) => Some(err),
+            StorageError::NotABundle(_)
+            | StorageError::CorruptProfiles(_)
+            | StorageError::InvalidProfileName(_)
+            | StorageError::UnknownProfile(_)
+            | StorageError::ProfileInUse(_) => None,
+            #[cfg(feature = "sqlite")]
+            StorageError::Sqlite(err) => Some(err),
+        }
+    }
+}
+
+impl From<MigrationError> for StorageError {
+    fn from(err: MigrationError) -> Self {
+        StorageError::Migration(err)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for StorageError {
+    fn from(err: rusqlite::Error) -> Self {
+        StorageError::Sqlite(err)
+    }
+}
@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::games::{Answer, Outcome, Question, Verdict};
+
+/// One answered question, as kept by backends that record history.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttemptRecord {
+    /// [`GameEngine::id`](crate::games::GameEngine::id) of the game played.
+    pub game: String,
+    pub item: Option<String>,
+    pub prompt: String,
+    pub given: String,
+    pub expected: String,
+    pub verdict: Verdict,
+    pub points: i32,
+    pub elapsed: Duration,
+    /// Unix timestamp of the answer, see [`scheduler::now`](crate::scheduler::now).
+    pub answered_at: u64,
+}
+
+impl AttemptRecord {
+    pub fn new(
+        game: &str,
+        question: &Question,
+        answer: &Answer,
+        outcome: &Outcome,
+        answered_at: u64,
+    ) -> Self {
+        Self {
+            game: game.to_string(),
+            item: question.item.clone(),
+            prompt: question.prompt.clone(),
+            given: answer.text.trim().to_string(),
+            expected: outcome.correct_answer.clone(),
+            verdict: outcome.verdict,
+            points: outcome.points,
+            elapsed: outcome.elapsed,
+            answered_at,
+        }
+    }
+}
+
+/// Which attempts to return from a history lookup, newest first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HistoryQuery {
+    pub game: Option<String>,
+    pub verdict: Option<Verdict>,
+    pub limit: Option<usize>,
+}
+
+impl HistoryQuery {
+    pub fn matches(&self, record: &AttemptRecord) -> bool {
+        self.game.as_ref().is_none_or(|game| *game == record.game)
+            && self.verdict.is_none_or(|verdict| verdict == record.verdict)
+    }
+
+    /// Runs the query over an in-memory log kept oldest first.
+    pub fn apply(&self, records: &[AttemptRecord]) -> Vec<AttemptRecord> {
+        records
+            .iter()
+            .rev()
+            .filter(|record| self.matches(record))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Aggregate figures over the recorded attempts of one game.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttemptStats {
+    pub attempts: u64,
+    pub correct: u64,
+    pub partial: u64,
+    pub average_elapsed: Duration,
+}
+
+impl AttemptStats {
+    pub fn from_records<'a>(records: impl IntoIterator<Item = &'a AttemptRecord>) -> Self {
+        let mut stats = AttemptStats::default();
+        let mut total_elapsed = Duration::ZERO;
+        for record in records {
+            stats.attempts += 1;
+            match record.verdict {
+                Verdict::Correct => stats.correct += 1,
+                Verdict::Partial => stats.partial += 1,
+                Verdict::Wrong => {}
+            }
+            total_elapsed += record.elapsed;
+        }
+        if stats.attempts > 0 {
+            stats.average_elapsed = total_elapsed / stats.attempts as u32;
+        }
+        stats
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.correct as f64 / self.attempts as f64
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+
+use serde_json::Value;
+
+/// Version written by this build; bump it together with a new entry in `STEPS`.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Upgrades a raw save file by exactly one version.
+type Step = fn(&mut Value) -> Result<(), String>;
+
+/// Migration from version `n` to `n + 1` lives at index `n`.
+const STEPS: &[Step] = &[v0_to_v1, v1_to_v2];
+
+/// Game ids in the order `App::new` registered them while saves were positional.
+const POSITIONAL_IDS: &[&str] = &[
+    "pi_challenge",
+    "greek_alphabet",
+    "music_tempo",
+    "math_tricks",
+    "physics_formulas",
+    "periodic_table",
+    "crypto_puzzle",
+];
+
+#[derive(Debug)]
+pub enum MigrationError {
+    /// The file is not valid JSON at all.
+    Parse(serde_json::Error),
+    /// The file was written by a newer build than this one.
+    UnsupportedVersion(u32),
+    /// A migration step rejected the data it was given.
+    Step { from: u32, reason: String },
+    /// The migrated data still does not match the current schema.
+    Schema(serde_json::Error),
+    /// The original file could not be backed up, so nothing was touched.
+    Backup(std::io::Error),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Parse(err) => write!(f, "save file is not valid JSON: {}", err),
+            MigrationError::UnsupportedVersion(version) => write!(
+                f,
+                "save file version {} is newer than supported version {}",
+                version, SCHEMA_VERSION
+            ),
+            MigrationError::Step { from, reason } => write!(
+                f,
+                "could not upgrade save file from version {}: {}",
+                from, reason
+            ),
+            MigrationError::Schema(err) => write!(f, "save file does not match schema: {}", err),
+            MigrationError::Backup(err) => write!(f, "could not back up save file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MigrationError::Parse(err) | MigrationError::Schema(err) => Some(err),
+            MigrationError::Backup(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Schema version recorded in a raw save file; files from before versioning are `0`.
+pub fn version_of(value: &Value) -> u32 {
+    value
+        .get("version")
+        .and_then(Value::as_u64)
+        .map(|version| version as u32)
+        .unwrap_or(0)
+}
+
+/// Runs every step needed to bring `value` up to [`SCHEMA_VERSION`].
+pub fn upgrade(mut value: Value) -> Result<Value, MigrationError> {
+    let mut version = version_of(&value);
+    if version > SCHEMA_VERSION {
+        return Err(MigrationError::UnsupportedVersion(version));
+    }
+
+    while version < SCHEMA_VERSION {
+        let step = STEPS[version as usize];
+        step(&mut value).map_err(|reason| MigrationError::Step {
+            from: version,
+            reason,
+        })?;
+        version += 1;
+        value["version"] = Value::from(version);
+    }
+    Ok(value)
+}
+
+/// Unversioned files: give every game an explicit, empty review schedule.
+fn v0_to_v1(value: &mut Value) -> Result<(), String> {
+    let root = value.as_object_mut().ok_or("top level is not an object")?;
+    let scores = root
+        .entry("scores")
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or("`scores` is not a list")?;
+
+    for state in scores {
+        let state = state
+            .as_object_mut()
+            .ok_or("score entry is not an object")?;
+        state
+            .entry("schedule")
+            .or_insert_with(|| serde_json::json!({ "cards": {} }));
+    }
+    Ok(())
+}
+
+/// Positional `scores` list becomes a `games` map keyed by game id.
+fn v1_to_v2(value: &mut Value) -> Result<(), String> {
+    let root = value.as_object_mut().ok_or("top level is not an object")?;
+    let scores = match root.remove("scores") {
+        Some(Value::Array(scores)) => scores,
+        Some(_) => return Err("`scores` is not a list".into()),
+        None => Vec::new(),
+    };
+
+    let games = scores
+        .into_iter()
+        .enumerate()
+        .map(|(index, state)| {
+            let id = POSITIONAL_IDS
+                .get(index)
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| format!("unknown_{}", index));
+            (id, state)
+        })
+        .collect();
+    root.insert("games".into(), Value::Object(games));
+    Ok(())
+}
@@ -0,0 +1,210 @@
+mod backend;
+mod bundle;
+mod error;
+mod history;
+mod migrate;
+mod profiles;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::daily::DailyProgress;
+use crate::games::GameState;
+
+pub use backend::{open, StorageBackend, JSON_HISTORY_PER_GAME};
+pub use bundle::{Bundle, BundlePreview, ImportMode};
+pub use error::StorageError;
+pub use history::{AttemptRecord, AttemptStats, HistoryQuery};
+pub use migrate::{MigrationError, SCHEMA_VERSION};
+pub use profiles::{Profile, Profiles};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStorage;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SaveData {
+    #[serde(default)]
+    pub version: u32,
+    /// Progress per game, keyed by [`GameEngine::id`](crate::games::GameEngine::id).
+    /// Entries for games this build does not know are kept as they are.
+    #[serde(default)]
+    pub games: BTreeMap<String, GameState>,
+    pub settings: Settings,
+    /// Answered questions, oldest first, up to [`JSON_HISTORY_PER_GAME`] for
+    /// each game. Backends with their own history store, such as SQLite,
+    /// leave this empty.
+    #[serde(default)]
+    pub history: Vec<AttemptRecord>,
+    /// How many of each game's oldest answers were dropped from `history`.
+    #[serde(default)]
+    pub trimmed_history: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub daily: DailyProgress,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            games: BTreeMap::new(),
+            settings: Settings::default(),
+            history: Vec::new(),
+            trimmed_history: BTreeMap::new(),
+            daily: DailyProgress::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Settings {
+    pub audio_enabled: bool,
+    pub theme: Theme,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            audio_enabled: true,
+            theme: Theme::Light,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+/// Number of rotated copies kept next to the save file by default.
+pub const DEFAULT_BACKUPS: usize = 3;
+
+pub struct Storage {
+    pub path: PathBuf,
+    /// How many previous saves are kept as `save.json.1` (newest) onwards.
+    pub backups: usize,
+}
+
+impl Storage {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            backups: DEFAULT_BACKUPS,
+        }
+    }
+
+    pub fn with_backups(mut self, backups: usize) -> Self {
+        self.backups = backups;
+        self
+    }
+
+    /// Reads the save file, upgrading it from older schema versions.
+    ///
+    /// A missing file yields fresh defaults. Before an older file is migrated
+    /// it is copied to [`Storage::backup_path`], and any failure is reported
+    /// rather than replaced with defaults. When the save file is missing or
+    /// unreadable the newest readable rotated backup is used instead.
+    pub fn load(&self) -> Result<SaveData, StorageError> {
+        let primary = match Self::load_file(&self.path) {
+            Ok(Some(data)) => return Ok(data),
+            // A file from a newer build must not be shadowed by older backups.
+            Err(err @ StorageError::Migration(MigrationError::UnsupportedVersion(_))) => {
+                return Err(err)
+            }
+            Ok(None) => None,
+            Err(err) => Some(err),
+        };
+
+        for index in 1..=self.backups {
+            if let Ok(Some(data)) = Self::load_file(&self.rotated_path(index)) {
+                return Ok(data);
+            }
+        }
+        match primary {
+            Some(err) => Err(err),
+            None => Ok(SaveData::default()),
+        }
+    }
+
+    /// Loads and migrates one file; `None` when it does not exist.
+    fn load_file(path: &Path) -> Result<Option<SaveData>, StorageError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(StorageError::io(path)(err)),
+        };
+
+        let value: Value = serde_json::from_str(&content).map_err(MigrationError::Parse)?;
+        let version = migrate::version_of(&value);
+        if version < SCHEMA_VERSION {
+            fs::copy(path, sibling(path, &format!("v{}.bak", version)))
+                .map_err(MigrationError::Backup)?;
+        }
+        let value = migrate::upgrade(value)?;
+        let data = serde_json::from_value(value).map_err(MigrationError::Schema)?;
+        Ok(Some(data))
+    }
+
+    /// Where the original file is kept before migrating away from `version`.
+    pub fn backup_path(&self, version: u32) -> PathBuf {
+        sibling(&self.path, &format!("v{}.bak", version))
+    }
+
+    /// Path of the `index`-th most recent previous save, starting at 1.
+    pub fn rotated_path(&self, index: usize) -> PathBuf {
+        sibling(&self.path, &index.to_string())
+    }
+
+    /// Writes `data` to a temporary file and renames it over the save file,
+    /// so a crash mid-write never leaves a truncated save behind.
+    pub fn save(&self, data: &SaveData) -> Result<(), StorageError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(StorageError::io(parent))?;
+        }
+        let json = serde_json::to_string_pretty(data).map_err(StorageError::Serialize)?;
+
+        let tmp = sibling(&self.path, "tmp");
+        File::create(&tmp)
+            .and_then(|mut file| {
+                file.write_all(json.as_bytes())?;
+                file.sync_all()
+            })
+            .map_err(StorageError::io(&tmp))?;
+
+        self.rotate()?;
+        fs::rename(&tmp, &self.path).map_err(StorageError::io(&self.path))
+    }
+
+    /// Shifts `save.json.1` to `save.json.2` and so on, dropping the oldest
+    /// copy, then copies the current save to `save.json.1`. The save file
+    /// itself stays in place until the new one is renamed over it.
+    fn rotate(&self) -> Result<(), StorageError> {
+        if self.backups == 0 || !self.path.exists() {
+            return Ok(());
+        }
+        for index in (1..self.backups).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(index + 1))
+                    .map_err(StorageError::io(&from))?;
+            }
+        }
+        let newest = self.rotated_path(1);
+        fs::copy(&self.path, &newest).map_err(StorageError::io(newest))?;
+        Ok(())
+    }
+}
+
+/// `save.json` with `.suffix` appended, in the same directory.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
@@ -0,0 +1,248 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::{open, StorageBackend, StorageError};
+
+const INDEX_FILE: &str = "profiles.json";
+const PROFILES_DIR: &str = "profiles";
+/// Profile created on first start, which also adopts a pre-profiles save.
+const DEFAULT_PROFILE: &str = "Default";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Directory name under `profiles/`; stays the same when renamed.
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileIndex {
+    #[serde(default)]
+    last_used: Option<String>,
+    #[serde(default)]
+    profiles: Vec<Profile>,
+}
+
+/// The players sharing one data directory. Each profile keeps its own save
+/// file in `profiles/<id>/`, and `profiles.json` lists them together with the
+/// one used last.
+pub struct Profiles {
+    root: PathBuf,
+    save_file: String,
+    index: ProfileIndex,
+    /// Profile chosen for this run only, in place of the one used last.
+    selected: Option<String>,
+}
+
+impl Profiles {
+    /// Reads the profile list under `root`, where every profile stores its
+    /// progress in a file called `save_file`.
+    ///
+    /// On the first start a "Default" profile is created and any save left
+    /// directly in `root` by an earlier version is moved into it.
+    pub fn open(root: PathBuf, save_file: &str) -> Result<Self, StorageError> {
+        let index_path = root.join(INDEX_FILE);
+        let index = match fs::read_to_string(&index_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|_| StorageError::CorruptProfiles(index_path))?,
+            Err(err) if err.kind() == ErrorKind::NotFound => ProfileIndex::default(),
+            Err(err) => return Err(StorageError::io(index_path)(err)),
+        };
+
+        let mut profiles = Self {
+            root,
+            save_file: save_file.to_string(),
+            index,
+            selected: None,
+        };
+        if profiles.index.profiles.is_empty() {
+            let profile = profiles.create(DEFAULT_PROFILE)?;
+            profiles.adopt_legacy_save(&profile.id)?;
+        }
+        if profiles.current_index().is_none() {
+            profiles.index.last_used = Some(profiles.index.profiles[0].id.clone());
+            profiles.write_index()?;
+        }
+        Ok(profiles)
+    }
+
+    pub fn list(&self) -> &[Profile] {
+        &self.index.profiles
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Profile> {
+        self.index.profiles.iter().find(|profile| profile.id == id)
+    }
+
+    /// Looks a profile up by id or, ignoring case, by name.
+    pub fn find(&self, id_or_name: &str) -> Option<&Profile> {
+        self.get(id_or_name).or_else(|| {
+            self.index
+                .profiles
+                .iter()
+                .find(|profile| profile.name.eq_ignore_ascii_case(id_or_name.trim()))
+        })
+    }
+
+    /// The profile used last, which is the one played when the app starts.
+    pub fn current(&self) -> &Profile {
+        &self.index.profiles[self.current_index().unwrap_or(0)]
+    }
+
+    fn current_index(&self) -> Option<usize> {
+        let current = self.selected.as_ref().or(self.index.last_used.as_ref())?;
+        self.index
+            .profiles
+            .iter()
+            .position(|profile| &profile.id == current)
+    }
+
+    pub fn save_path(&self, id: &str) -> PathBuf {
+        self.profile_dir(id).join(&self.save_file)
+    }
+
+    fn profile_dir(&self, id: &str) -> PathBuf {
+        self.root.join(PROFILES_DIR).join(id)
+    }
+
+    /// Opens the storage backend holding `id`'s progress.
+    pub fn open_storage(&self, id: &str) -> Result<Box<dyn StorageBackend>, StorageError> {
+        self.require(id)?;
+        open(&self.save_path(id))
+    }
+
+    pub fn create(&mut self, name: &str) -> Result<Profile, StorageError> {
+        let name = self.valid_name(name, None)?;
+        let base = slug(&name);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while self.get(&id).is_some() || self.profile_dir(&id).exists() {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+
+        let dir = self.profile_dir(&id);
+        fs::create_dir_all(&dir).map_err(StorageError::io(dir))?;
+        let profile = Profile { id, name };
+        self.index.profiles.push(profile.clone());
+        self.write_index()?;
+        Ok(profile)
+    }
+
+    pub fn rename(&mut self, id: &str, name: &str) -> Result<(), StorageError> {
+        self.require(id)?;
+        let name = self.valid_name(name, Some(id))?;
+        if let Some(profile) = self
+            .index
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.id == id)
+        {
+            profile.name = name;
+        }
+        self.write_index()
+    }
+
+    /// Removes a profile together with all of its progress. The current
+    /// profile cannot be deleted; switch to another one first.
+    pub fn delete(&mut self, id: &str) -> Result<(), StorageError> {
+        self.require(id)?;
+        if self.current().id == id {
+            return Err(StorageError::ProfileInUse(id.to_string()));
+        }
+
+        let dir = self.profile_dir(id);
+        match fs::remove_dir_all(&dir) {
+            Err(err) if err.kind() != ErrorKind::NotFound => {
+                return Err(StorageError::io(dir)(err))
+            }
+            _ => {}
+        }
+        self.index.profiles.retain(|profile| profile.id != id);
+        self.write_index()
+    }
+
+    /// Remembers `id` as the profile to start with next time.
+    pub fn set_current(&mut self, id: &str) -> Result<(), StorageError> {
+        self.require(id)?;
+        self.selected = None;
+        self.index.last_used = Some(id.to_string());
+        self.write_index()
+    }
+
+    /// Plays as `id` until the program exits, leaving the profile used last
+    /// as it is on disk.
+    pub fn select(&mut self, id: &str) -> Result<(), StorageError> {
+        self.require(id)?;
+        self.selected = Some(id.to_string());
+        Ok(())
+    }
+
+    fn require(&self, id: &str) -> Result<(), StorageError> {
+        match self.get(id) {
+            Some(_) => Ok(()),
+            None => Err(StorageError::UnknownProfile(id.to_string())),
+        }
+    }
+
+    /// Trims `name` and checks that no other profile is called the same.
+    fn valid_name(&self, name: &str, renaming: Option<&str>) -> Result<String, StorageError> {
+        let name = name.trim();
+        let taken = self.index.profiles.iter().any(|profile| {
+            Some(profile.id.as_str()) != renaming && profile.name.eq_ignore_ascii_case(name)
+        });
+        if name.is_empty() || taken {
+            return Err(StorageError::InvalidProfileName(name.to_string()));
+        }
+        Ok(name.to_string())
+    }
+
+    /// Moves `save.json`, its backups, or `save.db` from the data directory
+    /// root into the profile `id`.
+    fn adopt_legacy_save(&self, id: &str) -> Result<(), StorageError> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(StorageError::io(&self.root)(err)),
+        };
+        let dir = self.profile_dir(id);
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            if name.to_string_lossy().starts_with("save.") && entry.path().is_file() {
+                fs::rename(entry.path(), dir.join(&name))
+                    .map_err(StorageError::io(entry.path()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_index(&self) -> Result<(), StorageError> {
+        fs::create_dir_all(&self.root).map_err(StorageError::io(&self.root))?;
+        let json = serde_json::to_string_pretty(&self.index).map_err(StorageError::Serialize)?;
+        let path = self.root.join(INDEX_FILE);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, json).map_err(StorageError::io(&tmp))?;
+        fs::rename(&tmp, &path).map_err(StorageError::io(path))
+    }
+}
+
+/// Lower-case ASCII directory name for a profile called `name`.
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "profile".to_string()
+    } else {
+        slug.to_string()
+    }
+}
@@ -0,0 +1,210 @@
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+
+use super::migrate::{self, MigrationError, SCHEMA_VERSION};
+use super::{AttemptRecord, AttemptStats, HistoryQuery, SaveData, StorageBackend, StorageError};
+use crate::games::Verdict;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS save_data (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        json TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS save_data_backups (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        version INTEGER NOT NULL,
+        json TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS attempts (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        game TEXT NOT NULL,
+        item TEXT,
+        prompt TEXT NOT NULL,
+        given TEXT NOT NULL,
+        expected TEXT NOT NULL,
+        verdict TEXT NOT NULL,
+        points INTEGER NOT NULL,
+        latency_ms INTEGER NOT NULL,
+        answered_at INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS attempts_by_game ON attempts (game, answered_at);
+";
+
+/// SQLite backend: settings and totals as a JSON row, plus one row per answer.
+pub struct SqliteStorage {
+    connection: Connection,
+}
+
+impl SqliteStorage {
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(StorageError::io(parent))?;
+        }
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// A throwaway database, handy for tests.
+    pub fn in_memory() -> Result<Self, StorageError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, StorageError> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+}
+
+impl StorageBackend for SqliteStorage {
+    /// Reads the stored progress, upgrading it from older schema versions.
+    /// The original row is copied to `save_data_backups` before migrating.
+    fn load(&self) -> Result<SaveData, StorageError> {
+        let json: Option<String> = self
+            .connection
+            .query_row("SELECT json FROM save_data WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        let Some(json) = json else {
+            return Ok(SaveData::default());
+        };
+
+        let value: Value = serde_json::from_str(&json).map_err(MigrationError::Parse)?;
+        let version = migrate::version_of(&value);
+        if version < SCHEMA_VERSION {
+            self.connection.execute(
+                "INSERT INTO save_data_backups (version, json) VALUES (?1, ?2)",
+                params![version, json],
+            )?;
+        }
+        let value = migrate::upgrade(value)?;
+        Ok(serde_json::from_value(value).map_err(MigrationError::Schema)?)
+    }
+
+    fn save(&self, data: &SaveData) -> Result<(), StorageError> {
+        let json = serde_json::to_string(data).map_err(StorageError::Serialize)?;
+        self.connection.execute(
+            "INSERT INTO save_data (id, json) VALUES (1, ?1)
+             ON CONFLICT (id) DO UPDATE SET json = excluded.json",
+            params![json],
+        )?;
+        Ok(())
+    }
+
+    fn record_attempt(
+        &mut self,
+        _data: &mut SaveData,
+        record: AttemptRecord,
+    ) -> Result<(), StorageError> {
+        insert_attempt(&self.connection, &record)
+    }
+
+    fn history(
+        &self,
+        _data: &SaveData,
+        query: &HistoryQuery,
+    ) -> Result<Vec<AttemptRecord>, StorageError> {
+        let mut statement = self.connection.prepare(
+            "SELECT game, item, prompt, given, expected, verdict, points, latency_ms, answered_at
+             FROM attempts
+             WHERE (?1 IS NULL OR game = ?1) AND (?2 IS NULL OR verdict = ?2)
+             ORDER BY answered_at DESC, id DESC
+             LIMIT ?3",
+        )?;
+        let limit = query.limit.map_or(-1, |limit| limit as i64);
+        let verdict = query.verdict.map(verdict_name);
+        let rows = statement.query_map(params![query.game, verdict, limit], |row| {
+            let verdict: String = row.get(5)?;
+            Ok(AttemptRecord {
+                game: row.get(0)?,
+                item: row.get(1)?,
+                prompt: row.get(2)?,
+                given: row.get(3)?,
+                expected: row.get(4)?,
+                verdict: parse_verdict(&verdict),
+                points: row.get(6)?,
+                elapsed: Duration::from_millis(row.get::<_, i64>(7)? as u64),
+                answered_at: row.get::<_, i64>(8)? as u64,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    fn stats(&self, _data: &SaveData, game: &str) -> Result<AttemptStats, StorageError> {
+        let stats = self.connection.query_row(
+            "SELECT COUNT(*),
+                    TOTAL(verdict = 'correct'),
+                    TOTAL(verdict = 'partial'),
+                    COALESCE(AVG(latency_ms), 0)
+             FROM attempts WHERE game = ?1",
+            params![game],
+            |row| {
+                Ok(AttemptStats {
+                    attempts: row.get::<_, i64>(0)? as u64,
+                    correct: row.get::<_, f64>(1)? as u64,
+                    partial: row.get::<_, f64>(2)? as u64,
+                    average_elapsed: Duration::from_millis(row.get::<_, f64>(3)? as u64),
+                })
+            },
+        )?;
+        Ok(stats)
+    }
+
+    fn clear_history(&mut self, _data: &mut SaveData) -> Result<(), StorageError> {
+        self.connection.execute("DELETE FROM attempts", [])?;
+        Ok(())
+    }
+
+    /// Runs as one transaction, so a failed import keeps the old history.
+    fn replace_history(
+        &mut self,
+        _data: &mut SaveData,
+        records: Vec<AttemptRecord>,
+    ) -> Result<(), StorageError> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute("DELETE FROM attempts", [])?;
+        for record in &records {
+            insert_attempt(&transaction, record)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+fn insert_attempt(connection: &Connection, record: &AttemptRecord) -> Result<(), StorageError> {
+    connection.execute(
+        "INSERT INTO attempts
+            (game, item, prompt, given, expected, verdict, points, latency_ms, answered_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            record.game,
+            record.item,
+            record.prompt,
+            record.given,
+            record.expected,
+            verdict_name(record.verdict),
+            record.points,
+            record.elapsed.as_millis() as i64,
+            record.answered_at as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+fn verdict_name(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Partial => "partial",
+        Verdict::Wrong => "wrong",
+    }
+}
+
+fn parse_verdict(name: &str) -> Verdict {
+    match name {
+        "correct" => Verdict::Correct,
+        "partial" => Verdict::Partial,
+        _ => Verdict::Wrong,
+    }
+}
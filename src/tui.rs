@@ -0,0 +1,481 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::games::{self, Answer, GameModule, Outcome, Verdict};
+use crate::scheduler::{self, Clock};
+use crate::storage::{AttemptRecord, SaveData, StorageBackend, Theme};
+
+/// Main menu entries listed after the games.
+const MENU_EXTRAS: [&str; 3] = ["⚙ Settings", "🏆 Scoreboard", "Exit"];
+const SETTINGS_ROWS: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Screen {
+    MainMenu,
+    Game(usize),
+    Settings,
+    Scoreboard,
+}
+
+/// Full-screen terminal counterpart of [`App`](crate::ui::App), playing the
+/// same game modules against the same storage backend.
+pub struct Tui {
+    storage: Box<dyn StorageBackend>,
+    data: SaveData,
+    modules: Vec<Box<dyn GameModule + Send + Sync>>,
+    screen: Screen,
+    /// Highlighted row of the main menu or the settings list.
+    selected: usize,
+    input: String,
+    show_hints: bool,
+    /// Until when the study material of the current round stays on screen.
+    study_until: Option<Instant>,
+    round_started: Instant,
+    feedback: Option<Outcome>,
+    /// Progress could not be read; saving is held back so the file is not overwritten.
+    load_failed: bool,
+    /// Last storage problem, shown in the footer.
+    status: Option<String>,
+    running: bool,
+}
+
+/// Runs the terminal UI until the player exits, restoring the terminal afterwards.
+pub fn run(storage: Box<dyn StorageBackend>, seed: Option<u64>) -> io::Result<()> {
+    let mut tui = Tui::new(storage, seed);
+    let mut terminal = ratatui::init();
+    let result = tui.event_loop(&mut terminal);
+    ratatui::restore();
+    if let Some(status) = &tui.status {
+        eprintln!("MemoryKata: {}", status);
+    }
+    result
+}
+
+impl Tui {
+    /// Loads progress from `storage`; a `seed` makes the rounds reproducible.
+    pub fn new(storage: Box<dyn StorageBackend>, seed: Option<u64>) -> Self {
+        let mut tui = Self {
+            storage,
+            data: SaveData::default(),
+            modules: games::all(),
+            screen: Screen::MainMenu,
+            selected: 0,
+            input: String::new(),
+            show_hints: false,
+            study_until: None,
+            round_started: Instant::now(),
+            feedback: None,
+            load_failed: false,
+            status: None,
+            running: true,
+        };
+        match tui.storage.load() {
+            Ok(data) => tui.data = data,
+            Err(err) => {
+                tui.load_failed = true;
+                tui.status = Some(format!(
+                    "⚠ Could not load progress, so nothing will be saved: {}",
+                    err
+                ));
+            }
+        }
+        let started = scheduler::now();
+        for module in tui.modules.iter_mut() {
+            module.restore(tui.data.games.get(module.id()).cloned().unwrap_or_default());
+            module.share_progress(&tui.data.games);
+            if let Some(seed) = seed {
+                module.set_clock(Clock::Fixed(started));
+                module.reseed(seed);
+            }
+        }
+        tui
+    }
+
+    pub fn screen(&self) -> Screen {
+        self.screen
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while self.running {
+            terminal.draw(|frame| self.draw(frame))?;
+            // Wake up now and then to count down and hide study material.
+            if !event::poll(Duration::from_millis(200))? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                self.handle_key(key);
+            }
+        }
+        Ok(())
+    }
+
+    fn save(&mut self) {
+        if self.load_failed {
+            return;
+        }
+        for module in &self.modules {
+            self.data
+                .games
+                .insert(module.id().to_string(), module.state().clone());
+        }
+        self.status = self
+            .storage
+            .save(&self.data)
+            .err()
+            .map(|err| format!("⚠ Progress is not being saved: {}", err));
+    }
+
+    fn record_attempt(&mut self, record: AttemptRecord) {
+        if self.load_failed {
+            return;
+        }
+        match self.storage.record_attempt(&mut self.data, record) {
+            Ok(()) => self.save(),
+            Err(err) => self.status = Some(format!("⚠ Progress is not being saved: {}", err)),
+        }
+    }
+
+    fn quit(&mut self) {
+        self.save();
+        self.running = false;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit();
+            return;
+        }
+        match self.screen {
+            Screen::MainMenu => self.main_menu_key(key.code),
+            Screen::Game(index) => self.game_key(index, key),
+            Screen::Settings => self.settings_key(key.code),
+            Screen::Scoreboard => {
+                if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+                    self.screen = Screen::MainMenu;
+                }
+            }
+        }
+    }
+
+    fn main_menu_key(&mut self, code: KeyCode) {
+        let entries = self.modules.len() + MENU_EXTRAS.len();
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = (self.selected + entries - 1) % entries
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1) % entries,
+            KeyCode::Esc | KeyCode::Char('q') => self.quit(),
+            KeyCode::Enter => match self.selected.checked_sub(self.modules.len()) {
+                None => {
+                    self.screen = Screen::Game(self.selected);
+                    self.start_round();
+                }
+                Some(0) => {
+                    self.screen = Screen::Settings;
+                    self.selected = 0;
+                }
+                Some(1) => self.screen = Screen::Scoreboard,
+                _ => self.quit(),
+            },
+            _ => {}
+        }
+    }
+
+    fn game_key(&mut self, index: usize, key: KeyEvent) {
+        if index >= self.modules.len() {
+            self.screen = Screen::MainMenu;
+            return;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match (self.feedback.is_some(), key.code) {
+            (_, KeyCode::Esc) => {
+                self.screen = Screen::MainMenu;
+                self.selected = index;
+            }
+            (_, KeyCode::Char('r')) if ctrl => {
+                self.modules[index].reset();
+                self.start_round();
+            }
+            (true, KeyCode::Enter) => {
+                self.modules[index].next_round();
+                self.start_round();
+            }
+            // Hide the study material ahead of time.
+            (false, KeyCode::Enter) if self.studying() => self.study_until = Some(Instant::now()),
+            (false, _) if self.studying() => {}
+            (false, KeyCode::Enter) if !self.input.trim().is_empty() => self.submit(index),
+            (false, KeyCode::Tab) => self.show_hints = !self.show_hints,
+            (false, KeyCode::Backspace) => {
+                self.input.pop();
+            }
+            (false, KeyCode::Char(c)) if !ctrl => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    fn submit(&mut self, index: usize) {
+        let module = &mut self.modules[index];
+        let text = std::mem::take(&mut self.input);
+        // Answers are timed from when the study material disappeared.
+        let started = self.study_until.unwrap_or(self.round_started);
+        let answer = Answer::new(text, started.elapsed());
+        let outcome = module.check_answer(&answer);
+        let record = AttemptRecord::new(
+            module.id(),
+            module.question(),
+            &answer,
+            &outcome,
+            scheduler::now(),
+        );
+        self.feedback = Some(outcome);
+        self.record_attempt(record);
+    }
+
+    fn start_round(&mut self) {
+        self.input.clear();
+        self.show_hints = false;
+        self.feedback = None;
+        self.round_started = Instant::now();
+        let study = match self.screen {
+            Screen::Game(index) => self.modules[index].question().study.as_ref(),
+            _ => None,
+        };
+        self.study_until = study.map(|study| Instant::now() + study.duration);
+    }
+
+    fn studying(&self) -> bool {
+        self.study_until.is_some_and(|until| Instant::now() < until)
+    }
+
+    fn settings_key(&mut self, code: KeyCode) {
+        let settings = &mut self.data.settings;
+        match code {
+            KeyCode::Up | KeyCode::Char('k') | KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1) % SETTINGS_ROWS;
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => match self.selected {
+                0 => settings.audio_enabled = !settings.audio_enabled,
+                _ => {
+                    settings.theme = match settings.theme {
+                        Theme::Light => Theme::Dark,
+                        Theme::Dark => Theme::Light,
+                    }
+                }
+            },
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.screen = Screen::MainMenu;
+                self.selected = self.modules.len();
+                self.save();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let base = match self.data.settings.theme {
+            Theme::Light => Style::new().fg(Color::Black).bg(Color::White),
+            Theme::Dark => Style::new(),
+        };
+        frame.render_widget(Block::new().style(base), frame.area());
+
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from("MemoryKata 🧠".bold()),
+                Line::from("Sharpen your cognition with Unicode-powered mini-games ✨".italic()),
+            ]),
+            header,
+        );
+
+        match self.screen {
+            Screen::MainMenu => self.draw_main_menu(frame, body),
+            Screen::Game(index) => self.draw_game(frame, body, index),
+            Screen::Settings => self.draw_settings(frame, body),
+            Screen::Scoreboard => self.draw_scoreboard(frame, body),
+        }
+
+        let footer_line = match &self.status {
+            Some(status) => Line::from(status.as_str().red()),
+            None => Line::from(self.key_help().dim()),
+        };
+        frame.render_widget(Paragraph::new(footer_line), footer);
+    }
+
+    fn key_help(&self) -> &'static str {
+        match self.screen {
+            Screen::MainMenu => "↑↓ move · Enter select · q quit",
+            Screen::Game(_) if self.feedback.is_some() => "Enter next · Ctrl+R reset · Esc menu",
+            Screen::Game(_) if self.studying() => "Enter hide now · Ctrl+R reset · Esc menu",
+            Screen::Game(_) => "Enter check · Tab hint · Ctrl+R reset · Esc menu",
+            Screen::Settings => "↑↓ move · Enter toggle · Esc back",
+            Screen::Scoreboard => "Esc back",
+        }
+    }
+
+    fn draw_main_menu(&self, frame: &mut Frame, area: Rect) {
+        let games = self.modules.iter().map(|module| {
+            Text::from(vec![
+                Line::from(module.name().to_string()),
+                Line::from(format!("  {}", module.description()).italic()),
+            ])
+        });
+        let extras = MENU_EXTRAS.iter().map(|&entry| Text::from(entry));
+        let list = List::new(games.chain(extras))
+            .block(Block::bordered().title("Select a kata"))
+            .highlight_symbol("▶ ")
+            .highlight_style(Style::new().bold());
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_game(&self, frame: &mut Frame, area: Rect, index: usize) {
+        let Some(module) = self.modules.get(index) else {
+            return;
+        };
+        let question = module.question();
+        let study = question.study.as_ref().zip(self.study_until);
+        let mut lines = match study {
+            Some((study, until)) if self.feedback.is_none() && self.studying() => {
+                let left = until.saturating_duration_since(Instant::now());
+                let mut lines = vec![
+                    Line::from("Memorise:"),
+                    Line::from(study.content.clone().bold()),
+                ];
+                if let Some(note) = &study.note {
+                    lines.push(Line::from(note.clone().italic()));
+                }
+                lines.push(Line::default());
+                lines.push(Line::from(
+                    format!("Hidden in {:.1}s", left.as_secs_f32()).dim(),
+                ));
+                lines
+            }
+            _ => vec![Line::from(question.prompt.clone()), Line::default()],
+        };
+        match &self.feedback {
+            Some(outcome) => feedback_lines(outcome, &mut lines),
+            None if self.studying() => {}
+            None => {
+                lines.push(Line::from(format!("> {}█", self.input)));
+                if self.show_hints {
+                    if question.hints.is_empty() {
+                        lines.push(Line::from("No hint for this round.".dim()));
+                    }
+                    for hint in &question.hints {
+                        lines.push(Line::from(format!("Hint: {}", hint).dim()));
+                    }
+                }
+            }
+        }
+        let state = module.state();
+        lines.push(Line::default());
+        lines.push(Line::from(format!(
+            "Score: {} | Attempts: {}",
+            state.score, state.attempts
+        )));
+
+        let paragraph = Paragraph::new(lines)
+            .block(Block::bordered().title(module.name().to_string()))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+    }
+
+    fn draw_settings(&self, frame: &mut Frame, area: Rect) {
+        let settings = &self.data.settings;
+        let rows = [
+            format!(
+                "Sound effects ♫: {}",
+                if settings.audio_enabled { "on" } else { "off" }
+            ),
+            format!(
+                "Theme: {}",
+                match settings.theme {
+                    Theme::Light => "☀ Light",
+                    Theme::Dark => "🌙 Dark",
+                }
+            ),
+        ];
+        let list = List::new(rows)
+            .block(Block::bordered().title("⚙ Settings"))
+            .highlight_symbol("▶ ")
+            .highlight_style(Style::new().bold());
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_scoreboard(&self, frame: &mut Frame, area: Rect) {
+        let now = scheduler::now();
+        let mut lines = Vec::new();
+        for module in &self.modules {
+            let state = module.state();
+            lines.push(Line::from(format!(
+                "{} → Score: {} | Attempts: {}",
+                module.name(),
+                state.score,
+                state.attempts
+            )));
+            let due = state.schedule.due_count(now);
+            if due > 0 {
+                lines.push(Line::from(format!("  ⏰ {} item(s) due for review", due)));
+            }
+            lines.push(Line::from(format!("  {}", module.description()).italic()));
+        }
+        let paragraph = Paragraph::new(lines)
+            .block(Block::bordered().title("🏆 Scoreboard"))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+    }
+}
+
+/// Text version of the window's feedback panel.
+fn feedback_lines(outcome: &Outcome, lines: &mut Vec<Line<'static>>) {
+    let color = match outcome.verdict {
+        Verdict::Correct => Color::Green,
+        Verdict::Partial => Color::Yellow,
+        Verdict::Wrong => Color::Red,
+    };
+    lines.push(Line::from(
+        format!("{} {:+} points", outcome.verdict.symbol(), outcome.points)
+            .fg(color)
+            .bold(),
+    ));
+    lines.push(Line::from(format!("Answer: {}", outcome.correct_answer)));
+    if !outcome.marks.is_empty() {
+        let mut spans = vec![Span::raw("Yours:  ")];
+        spans.extend(outcome.marks.iter().map(|mark| {
+            let color = if mark.is_correct() {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            mark.shown().to_string().fg(color)
+        }));
+        lines.push(Line::from(spans));
+    }
+    if let Some(explanation) = &outcome.explanation {
+        lines.push(Line::from(explanation.clone().italic()));
+    }
+    lines.push(Line::from(format!(
+        "Time: {:.1}s",
+        outcome.elapsed.as_secs_f32()
+    )));
+}
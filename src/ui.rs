@@ -1,61 +1,228 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
 use eframe::egui::{self, Align, Layout, RichText, ViewportCommand};
 
-use crate::games::{self, GameModule, GameState};
-use crate::storage::{SaveData, Storage, Theme};
+use crate::daily::{self, DailyChallenge, DAILY_ROUNDS};
+use crate::games::{self, Answer, GameModule, Outcome, Verdict};
+use crate::scheduler::{self, Clock};
+use crate::storage::{
+    AttemptRecord, Bundle, HistoryQuery, ImportMode, Profiles, SaveData, StorageBackend,
+    StorageError, Theme, JSON_HISTORY_PER_GAME,
+};
 
 pub enum View {
+    /// Who is playing; shown on startup with the last-used profile first.
+    Profiles,
     MainMenu,
     Game(usize),
+    /// Today's run of the daily challenge.
+    Daily,
     Settings,
     Scoreboard,
+    History,
+}
+
+/// A storage failure shown in the banner until it is retried or dismissed.
+enum StorageProblem {
+    /// Progress could not be read; saving is held back so the file is not overwritten.
+    Load(StorageError),
+    Save(StorageError),
 }
 
 pub struct App {
-    pub storage: Storage,
+    pub profiles: Profiles,
+    /// Backend of the current profile.
+    pub storage: Box<dyn StorageBackend>,
     pub data: SaveData,
     pub view: View,
     pub modules: Vec<Box<dyn GameModule + Send + Sync>>,
+    /// Daily challenge in progress, rebuilt when the day changes.
+    daily: Option<DailyChallenge>,
     active_theme: Option<Theme>,
+    round_started: Instant,
+    feedback: Option<Outcome>,
+    storage_problem: Option<StorageProblem>,
+    history_filter: HistoryQuery,
+    /// Rows on the history screen, looked up again when it is opened or
+    /// its filters change.
+    history: Option<Result<Vec<AttemptRecord>, String>>,
+    bundle_path: String,
+    /// Bundle read from `bundle_path`, waiting for a replace or merge decision.
+    import_preview: Option<Bundle>,
+    bundle_status: Option<String>,
+    new_profile_name: String,
+    /// Profile id and the name being typed for it.
+    renaming: Option<(String, String)>,
+    /// Profile id waiting for the delete to be confirmed.
+    confirm_delete: Option<String>,
+    profile_status: Option<String>,
+    /// Seed from `--seed`, applied whenever progress is (re)loaded.
+    seed: Option<u64>,
 }
 
 impl App {
-    pub fn new(storage: Storage) -> Self {
-        let modules: Vec<Box<dyn GameModule + Send + Sync>> = vec![
-            Box::new(games::pi_challenge::PiChallenge::new()),
-            Box::new(games::greek_alphabet::GreekAlphabet::new()),
-            Box::new(games::music_tempo::MusicTempo::new()),
-            Box::new(games::math_tricks::MathTricks::new()),
-            Box::new(games::physics_formulas::PhysicsFormulas::new()),
-            Box::new(games::periodic_table::PeriodicTable::new()),
-            Box::new(games::crypto_puzzle::CryptoPuzzle::new()),
-        ];
-
-        let data = storage.load();
+    /// Starts on the profile picker with `storage` holding the progress of
+    /// [`Profiles::current`]. A `seed` makes the rounds reproducible.
+    pub fn new(profiles: Profiles, storage: Box<dyn StorageBackend>, seed: Option<u64>) -> Self {
         let mut app = Self {
+            profiles,
             storage,
-            data,
-            view: View::MainMenu,
-            modules,
+            data: SaveData::default(),
+            view: View::Profiles,
+            modules: games::all(),
+            daily: None,
             active_theme: None,
+            round_started: Instant::now(),
+            feedback: None,
+            storage_problem: None,
+            history_filter: HistoryQuery {
+                limit: Some(HISTORY_ROWS),
+                ..Default::default()
+            },
+            history: None,
+            bundle_path: dirs::home_dir()
+                .unwrap_or_default()
+                .join("memorykata-progress.json")
+                .display()
+                .to_string(),
+            import_preview: None,
+            bundle_status: None,
+            new_profile_name: String::new(),
+            renaming: None,
+            confirm_delete: None,
+            profile_status: None,
+            seed,
         };
-        app.hydrate_scores();
+        app.reload();
         app
     }
 
+    /// Reads progress from disk, keeping the current state if that fails.
+    pub fn reload(&mut self) {
+        match self.storage.load() {
+            Ok(data) => {
+                self.data = data;
+                self.storage_problem = None;
+                self.hydrate_scores();
+            }
+            Err(err) => self.storage_problem = Some(StorageProblem::Load(err)),
+        }
+    }
+
+    /// Drops the banner's problem. Dropping a load problem re-enables
+    /// saving; the unreadable file is rotated into the backups on the next
+    /// save.
+    pub fn dismiss_storage_problem(&mut self) {
+        self.storage_problem = None;
+    }
+
     pub fn save(&mut self) {
-        self.data.scores = self.modules.iter().map(|m| m.state().clone()).collect();
-        self.storage.save(&self.data);
+        if let Some(StorageProblem::Load(_)) = self.storage_problem {
+            return;
+        }
+        self.sync_scores();
+        self.storage_problem = self.storage.save(&self.data).err().map(StorageProblem::Save);
     }
 
-    fn hydrate_scores(&mut self) {
-        if self.data.scores.len() != self.modules.len() {
+    /// Copies every module's live state into `data`.
+    fn sync_scores(&mut self) {
+        for module in &self.modules {
             self.data
-                .scores
-                .resize(self.modules.len(), GameState::default());
+                .games
+                .insert(module.id().to_string(), module.state().clone());
         }
+    }
 
-        for (module, saved_state) in self.modules.iter_mut().zip(self.data.scores.iter()) {
-            *module.state_mut() = saved_state.clone();
+    /// Saves the current profile and continues with the progress of `id`.
+    pub fn switch_profile(&mut self, id: &str) {
+        if id != self.profiles.current().id {
+            let storage = match self.profiles.open_storage(id) {
+                Ok(storage) => storage,
+                Err(err) => {
+                    self.profile_status = Some(format!("⚠ {}", err));
+                    return;
+                }
+            };
+            self.save();
+            self.storage = storage;
+            self.feedback = None;
+            self.import_preview = None;
+            self.daily = None;
+            // Start from nothing so that a failed load cannot leave the
+            // previous profile's progress in place to be saved over this one.
+            self.data = SaveData::default();
+            self.modules = games::all();
+            self.hydrate_scores();
+            self.reload();
+        }
+        if let Err(err) = self.profiles.set_current(id) {
+            self.profile_status = Some(format!("⚠ {}", err));
+            return;
+        }
+        self.profile_status = None;
+        self.view = View::MainMenu;
+    }
+
+    fn export_bundle(&mut self) {
+        self.sync_scores();
+        let path = PathBuf::from(self.bundle_path.trim());
+        let exported = Bundle::export(self.storage.as_ref(), &self.data, scheduler::now())
+            .and_then(|bundle| bundle.write(&path));
+        self.bundle_status = Some(match exported {
+            Ok(()) => format!("✓ Exported to {}", path.display()),
+            Err(err) => format!("⚠ {}", err),
+        });
+    }
+
+    fn preview_import(&mut self) {
+        match Bundle::read(&PathBuf::from(self.bundle_path.trim())) {
+            Ok(bundle) => {
+                self.import_preview = Some(bundle);
+                self.bundle_status = None;
+            }
+            Err(err) => self.bundle_status = Some(format!("⚠ {}", err)),
+        }
+    }
+
+    fn import_bundle(&mut self, mode: ImportMode) {
+        let Some(bundle) = self.import_preview.take() else {
+            return;
+        };
+        self.sync_scores();
+        match bundle.import(self.storage.as_mut(), &mut self.data, mode) {
+            Ok(()) => {
+                self.hydrate_scores();
+                self.save();
+                self.bundle_status = Some(match self.storage_problem {
+                    None => "✓ Progress imported".into(),
+                    Some(_) => "⚠ Progress imported but not saved; see the warning above".into(),
+                });
+            }
+            Err(err) => self.bundle_status = Some(format!("⚠ {}", err)),
+        }
+    }
+
+    /// Hands a graded round to the storage backend and saves the new totals.
+    fn record_attempt(&mut self, record: AttemptRecord) {
+        if let Some(StorageProblem::Load(_)) = self.storage_problem {
+            return;
+        }
+        match self.storage.record_attempt(&mut self.data, record) {
+            Ok(()) => self.save(),
+            Err(err) => self.storage_problem = Some(StorageProblem::Save(err)),
+        }
+    }
+
+    fn hydrate_scores(&mut self) {
+        let loaded = scheduler::now();
+        for module in self.modules.iter_mut() {
+            module.restore(self.data.games.get(module.id()).cloned().unwrap_or_default());
+            module.share_progress(&self.data.games);
+            if let Some(seed) = self.seed {
+                module.set_clock(Clock::Fixed(loaded));
+                module.reseed(seed);
+            }
         }
     }
 
@@ -77,35 +244,181 @@ impl App {
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         self.sync_theme(ctx);
+        self.render_storage_banner(ctx);
         match self.view {
+            View::Profiles => self.render_profiles(ctx, frame),
             View::MainMenu => self.render_main_menu(ctx, frame),
             View::Settings => self.render_settings(ctx, frame),
             View::Scoreboard => self.render_scoreboard(ctx, frame),
+            View::History => self.render_history(ctx, frame),
             View::Game(index) => self.render_game(ctx, frame, index),
+            View::Daily => self.render_daily(ctx, frame),
         }
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
         self.save();
+        if let Some(StorageProblem::Save(err)) = &self.storage_problem {
+            eprintln!("MemoryKata: progress was not saved: {}", err);
+        }
     }
 }
 
 impl App {
+    fn render_storage_banner(&mut self, ctx: &egui::Context) {
+        let Some(problem) = &self.storage_problem else {
+            return;
+        };
+        let (message, dismiss_label) = match problem {
+            StorageProblem::Load(err) => {
+                (format!("⚠ Could not load progress: {}", err), "Start fresh")
+            }
+            StorageProblem::Save(err) => {
+                (format!("⚠ Progress is not being saved: {}", err), "Dismiss")
+            }
+        };
+
+        let mut retry = false;
+        let mut dismiss = false;
+        egui::TopBottomPanel::top("storage_banner").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+                retry = ui.button("Retry ⟳").clicked();
+                dismiss = ui.button(dismiss_label).clicked();
+            });
+        });
+
+        if retry {
+            match self.storage_problem {
+                Some(StorageProblem::Load(_)) => self.reload(),
+                _ => self.save(),
+            }
+        } else if dismiss {
+            self.dismiss_storage_problem();
+        }
+    }
+
+    fn render_profiles(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading(RichText::new("MemoryKata 🧠").strong().size(32.0));
+            ui.heading("👤 Who is playing?");
+            ui.add_space(8.0);
+
+            let current = self.profiles.current().id.clone();
+            let mut chosen = None;
+            let mut rename = None;
+            let mut delete = None;
+            for profile in self.profiles.list() {
+                ui.horizontal(|ui| {
+                    match &mut self.renaming {
+                        Some((id, name)) if *id == profile.id => {
+                            ui.text_edit_singleline(name);
+                            if ui.button("Save").clicked() {
+                                rename = Some((id.clone(), name.clone()));
+                            }
+                            if ui.button("Cancel").clicked() {
+                                self.renaming = None;
+                            }
+                        }
+                        _ => {
+                            let label = if profile.id == current {
+                                RichText::new(format!("▶ {}", profile.name)).strong()
+                            } else {
+                                RichText::new(&profile.name)
+                            };
+                            if ui.button(label).clicked() {
+                                chosen = Some(profile.id.clone());
+                            }
+                            if ui.small_button("✏ Rename").clicked() {
+                                self.renaming = Some((profile.id.clone(), profile.name.clone()));
+                            }
+                            if profile.id != current && ui.small_button("🗑 Delete").clicked() {
+                                self.confirm_delete = Some(profile.id.clone());
+                            }
+                        }
+                    }
+                });
+                if self.confirm_delete.as_deref() == Some(profile.id.as_str()) {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Delete {} and all of their progress?", profile.name));
+                        if ui.button("Delete").clicked() {
+                            delete = Some(profile.id.clone());
+                        }
+                        if ui.button("Keep").clicked() {
+                            self.confirm_delete = None;
+                        }
+                    });
+                }
+            }
+
+            ui.separator();
+            let mut create = false;
+            ui.horizontal(|ui| {
+                ui.label("New profile:");
+                let response = ui.text_edit_singleline(&mut self.new_profile_name);
+                let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                create = ui.button("Create ➕").clicked() || entered;
+            });
+            if let Some(status) = &self.profile_status {
+                ui.label(status);
+            }
+
+            let result = if let Some((id, name)) = rename {
+                self.renaming = None;
+                self.profiles.rename(&id, &name)
+            } else if let Some(id) = delete {
+                self.confirm_delete = None;
+                self.profiles.delete(&id)
+            } else if create {
+                self.profiles.create(&self.new_profile_name).map(|profile| {
+                    self.new_profile_name.clear();
+                    chosen = Some(profile.id);
+                })
+            } else {
+                Ok(())
+            };
+            match result {
+                Ok(()) => {
+                    if let Some(id) = chosen {
+                        self.switch_profile(&id);
+                    }
+                }
+                Err(err) => self.profile_status = Some(format!("⚠ {}", err)),
+            }
+        });
+    }
+
     fn render_main_menu(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading(RichText::new("MemoryKata 🧠").strong().size(32.0));
             ui.label("Sharpen your cognition with Unicode-powered mini-games ✨");
+            let profile = format!("👤 {} (switch)", self.profiles.current().name);
+            if ui.small_button(profile).clicked() {
+                self.view = View::Profiles;
+            }
+
+            ui.add_space(16.0);
+            self.render_daily_card(ui);
 
             ui.add_space(16.0);
             ui.heading("Select a kata:");
 
+            let mut chosen = None;
             for (index, module) in self.modules.iter().enumerate() {
                 if ui.button(module.name()).clicked() {
-                    self.view = View::Game(index);
+                    chosen = Some(index);
                 }
                 ui.label(RichText::new(module.description()).italics());
                 ui.separator();
             }
+            if let Some(index) = chosen {
+                self.view = View::Game(index);
+                self.round_started = Instant::now();
+                self.feedback = None;
+                // Pick up pegs changed in the Major System since loading.
+                self.sync_scores();
+                self.modules[index].share_progress(&self.data.games);
+            }
 
             ui.with_layout(Layout::top_down(Align::LEFT), |ui| {
                 if ui.button("⚙️ Settings").clicked() {
@@ -121,6 +434,74 @@ impl App {
         });
     }
 
+    /// Today's challenge with the streak, at the top of the main menu.
+    fn render_daily_card(&mut self, ui: &mut egui::Ui) {
+        let today = daily::day_of(scheduler::now());
+        let progress = &self.data.daily;
+        let mut start = false;
+        ui.group(|ui| {
+            ui.heading(format!("📅 Daily challenge · {}", daily::date(today)));
+            match progress.result(today) {
+                Some(result) if result.is_complete() => {
+                    ui.label(
+                        RichText::new(format!(
+                            "✓ Done for today: {}/{} correct, {:+} points",
+                            result.correct, DAILY_ROUNDS, result.score
+                        ))
+                        .strong(),
+                    );
+                    ui.label(RichText::new("Come back tomorrow for a new set.").italics());
+                }
+                Some(result) => {
+                    let label = format!("Resume ▶ (round {}/{})", result.answered + 1, DAILY_ROUNDS);
+                    start = ui.button(RichText::new(label).size(18.0)).clicked();
+                }
+                None => {
+                    let label = format!("Play today's {} rounds ▶", DAILY_ROUNDS);
+                    start = ui.button(RichText::new(label).size(18.0)).clicked();
+                }
+            }
+            ui.label(format!(
+                "🔥 Streak: {} · best {}",
+                progress.current_streak(today),
+                progress.best_streak
+            ));
+        });
+
+        if start {
+            self.start_daily();
+        }
+    }
+
+    /// Opens today's challenge, resuming it if it was already started.
+    pub fn start_daily(&mut self) {
+        let today = daily::day_of(scheduler::now());
+        if self.daily.as_ref().map(|challenge| challenge.result().day) != Some(today) {
+            self.daily = Some(DailyChallenge::new(today, &self.data.daily));
+        }
+        self.view = View::Daily;
+        self.round_started = Instant::now();
+        self.feedback = None;
+    }
+
+    /// Grades `text` against the current daily round, recording it in the
+    /// history like any other answer.
+    pub fn answer_daily(&mut self, text: String) {
+        let Some(challenge) = self.daily.as_mut() else {
+            return;
+        };
+        let started = challenge.game().study_ended().unwrap_or(self.round_started);
+        let answer = Answer::new(text, started.elapsed());
+        let outcome = challenge.check_answer(&answer, &mut self.data.daily);
+        let record = challenge.answered_game().map(|game| {
+            AttemptRecord::new(game.id(), game.question(), &answer, &outcome, scheduler::now())
+        });
+        self.feedback = Some(outcome);
+        if let Some(record) = record {
+            self.record_attempt(record);
+        }
+    }
+
     fn render_settings(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("⚙️ Settings");
@@ -131,9 +512,60 @@ impl App {
                 ui.selectable_value(&mut self.data.settings.theme, crate::storage::Theme::Dark, "🌙 Dark");
             });
 
+            ui.separator();
+            ui.heading("📦 Progress bundle");
+            ui.horizontal(|ui| {
+                ui.label("File:");
+                ui.text_edit_singleline(&mut self.bundle_path);
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Export ⬆").clicked() {
+                    self.export_bundle();
+                }
+                if ui.button("Import ⬇").clicked() {
+                    self.preview_import();
+                }
+            });
+
+            let mut chosen_mode = None;
+            let mut cancelled = false;
+            if let Some(bundle) = &self.import_preview {
+                let preview = bundle.preview();
+                ui.group(|ui| {
+                    ui.label(format!(
+                        "Exported {} · {} games · score {} · {} attempts · {} answers in history",
+                        time_ago(scheduler::now(), preview.exported_at),
+                        preview.games,
+                        preview.total_score,
+                        preview.total_attempts,
+                        preview.history_entries
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button("Replace current progress").clicked() {
+                            chosen_mode = Some(ImportMode::Replace);
+                        }
+                        if ui.button("Merge into current progress").clicked() {
+                            chosen_mode = Some(ImportMode::Merge);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            }
+            if let Some(mode) = chosen_mode {
+                self.import_bundle(mode);
+            } else if cancelled {
+                self.import_preview = None;
+            }
+            if let Some(status) = &self.bundle_status {
+                ui.label(status);
+            }
+
             ui.separator();
             if ui.button("Back").clicked() {
                 self.view = View::MainMenu;
+                self.save();
             }
         });
     }
@@ -146,24 +578,146 @@ impl App {
             for module in &self.modules {
                 let state = module.state();
                 ui.label(format!("{} → Score: {} | Attempts: {}", module.name(), state.score, state.attempts));
+                let due = state.schedule.due_count(scheduler::now());
+                if due > 0 {
+                    ui.label(format!("⏰ {} item(s) due for review", due));
+                }
                 ui.label(RichText::new(module.description()).italics());
                 ui.separator();
             }
 
+            if ui.button("📜 History").clicked() {
+                self.view = View::History;
+                self.history = None;
+            }
             if ui.button("Back").clicked() {
                 self.view = View::MainMenu;
             }
         });
     }
 
+    fn render_history(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("📜 History");
+
+            let filter = &mut self.history_filter;
+            let shown = filter.clone();
+            let game_name = |id: &str| {
+                self.modules
+                    .iter()
+                    .find(|module| module.id() == id)
+                    .map_or(id.to_string(), |module| module.name().to_string())
+            };
+            ui.horizontal(|ui| {
+                ui.label("Game:");
+                let selected = filter.game.as_deref().map_or("All games".into(), game_name);
+                egui::ComboBox::from_id_source("history_game")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut filter.game, None, "All games");
+                        for module in &self.modules {
+                            let id = Some(module.id().to_string());
+                            ui.selectable_value(&mut filter.game, id, module.name());
+                        }
+                    });
+
+                ui.label("Outcome:");
+                let selected = filter.verdict.map_or("Any", verdict_label);
+                egui::ComboBox::from_id_source("history_verdict")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut filter.verdict, None, "Any");
+                        for verdict in [Verdict::Correct, Verdict::Partial, Verdict::Wrong] {
+                            ui.selectable_value(
+                                &mut filter.verdict,
+                                Some(verdict),
+                                verdict_label(verdict),
+                            );
+                        }
+                    });
+            });
+            ui.separator();
+
+            if *filter != shown {
+                self.history = None;
+            }
+            let history = self.history.get_or_insert_with(|| {
+                self.storage
+                    .history(&self.data, filter)
+                    .map_err(|err| err.to_string())
+            });
+            let trimmed: u64 = self
+                .data
+                .trimmed_history
+                .iter()
+                .filter(|(id, _)| filter.game.as_ref().is_none_or(|game| game == *id))
+                .map(|(_, count)| count)
+                .sum();
+            if trimmed > 0 {
+                ui.label(
+                    RichText::new(format!(
+                        "Only the latest {} answers of each game are kept; {} older ones were dropped.",
+                        JSON_HISTORY_PER_GAME, trimmed
+                    ))
+                    .italics(),
+                );
+            }
+            match history {
+                Ok(records) if records.is_empty() => {
+                    ui.label("No answers recorded yet.");
+                }
+                Ok(records) => {
+                    let now = scheduler::now();
+                    egui::ScrollArea::vertical()
+                        .max_height(ui.available_height() - 40.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("history_grid").striped(true).show(ui, |ui| {
+                                for heading in HISTORY_COLUMNS {
+                                    ui.strong(heading);
+                                }
+                                ui.end_row();
+                                for record in records.iter() {
+                                    ui.label(record.verdict.symbol());
+                                    ui.label(time_ago(now, record.answered_at));
+                                    ui.label(game_name(&record.game));
+                                    ui.label(&record.prompt);
+                                    ui.label(&record.given);
+                                    ui.label(&record.expected);
+                                    ui.label(format!("{:.1}s", record.elapsed.as_secs_f32()));
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                }
+                Err(err) => {
+                    ui.colored_label(ui.visuals().error_fg_color, format!("⚠ {}", err));
+                }
+            }
+
+            ui.separator();
+            if ui.button("Back").clicked() {
+                self.view = View::Scoreboard;
+            }
+        });
+    }
+
     fn render_game(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame, index: usize) {
         if let Some(module) = self.modules.get_mut(index) {
             let mut back_to_menu = false;
             let mut reset_requested = false;
+            let mut submitted = None;
+            let mut next_requested = false;
 
             {
                 egui::CentralPanel::default().show(ctx, |ui| {
-                    module.update(ui, frame);
+                    match &self.feedback {
+                        Some(outcome) => {
+                            ui.heading(module.name());
+                            ui.label(&module.question().prompt);
+                            next_requested = render_feedback(ui, outcome);
+                        }
+                        None => submitted = module.update(ui, frame),
+                    }
                     ui.separator();
                     if ui.button("Return to menu ↩").clicked() {
                         back_to_menu = true;
@@ -174,12 +728,37 @@ impl App {
                 });
             }
 
+            let mut attempt = None;
+            if let Some(text) = submitted {
+                // Answers are timed from when the study material disappeared.
+                let started = module.study_ended().unwrap_or(self.round_started);
+                let answer = Answer::new(text, started.elapsed());
+                let outcome = module.check_answer(&answer);
+                attempt = Some(AttemptRecord::new(
+                    module.id(),
+                    module.question(),
+                    &answer,
+                    &outcome,
+                    scheduler::now(),
+                ));
+                self.feedback = Some(outcome);
+            }
+            if next_requested {
+                module.next_round();
+                self.round_started = Instant::now();
+                self.feedback = None;
+            }
             if reset_requested {
                 module.reset();
+                self.round_started = Instant::now();
+                self.feedback = None;
             }
             if back_to_menu {
                 self.view = View::MainMenu;
             }
+            if let Some(record) = attempt {
+                self.record_attempt(record);
+            }
         } else {
             egui::CentralPanel::default().show(ctx, |ui| {
                 ui.label("Module not found");
@@ -190,3 +769,134 @@ impl App {
         }
     }
 }
+
+impl App {
+    /// Plays the daily challenge. Results go to `data.daily` after every
+    /// answer, so leaving and coming back the same day resumes the run.
+    fn render_daily(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let Some(challenge) = self.daily.as_mut() else {
+            self.view = View::MainMenu;
+            return;
+        };
+        let mut back_to_menu = false;
+        let mut submitted = None;
+        let mut next_requested = false;
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let result = challenge.result().clone();
+            ui.heading(format!("📅 Daily challenge · {}", daily::date(result.day)));
+            match &self.feedback {
+                Some(outcome) => {
+                    ui.label(format!("Round {}/{}", result.answered, DAILY_ROUNDS));
+                    if let Some(game) = challenge.answered_game() {
+                        ui.label(&game.question().prompt);
+                    }
+                    next_requested = render_feedback(ui, outcome);
+                }
+                None if challenge.is_complete() => {
+                    ui.label(
+                        RichText::new(format!(
+                            "Finished: {}/{} correct, {:+} points",
+                            result.correct, DAILY_ROUNDS, result.score
+                        ))
+                        .size(24.0),
+                    );
+                    ui.label(format!(
+                        "🔥 Streak: {}",
+                        self.data.daily.current_streak(result.day)
+                    ));
+                }
+                None => {
+                    ui.label(format!(
+                        "Round {}/{} · {}",
+                        result.answered + 1,
+                        DAILY_ROUNDS,
+                        challenge.game().name()
+                    ));
+                    submitted = challenge.game().update(ui, frame);
+                }
+            }
+            ui.separator();
+            if ui.button("Return to menu ↩").clicked() {
+                back_to_menu = true;
+            }
+        });
+
+        if let Some(text) = submitted {
+            self.answer_daily(text);
+        }
+        if next_requested {
+            if let Some(challenge) = self.daily.as_mut() {
+                challenge.next_round();
+            }
+            self.round_started = Instant::now();
+            self.feedback = None;
+        }
+        if back_to_menu {
+            // An answered round is done with even if "Next" was never pressed.
+            if self.feedback.take().is_some() {
+                if let Some(challenge) = self.daily.as_mut() {
+                    challenge.next_round();
+                }
+            }
+            self.view = View::MainMenu;
+        }
+    }
+}
+
+/// Most recent answers listed in the history view.
+const HISTORY_ROWS: usize = 200;
+const HISTORY_COLUMNS: [&str; 7] = ["", "When", "Game", "Prompt", "Given", "Expected", "Time"];
+
+fn verdict_label(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "✓ Correct",
+        Verdict::Partial => "≈ Partial",
+        Verdict::Wrong => "✗ Wrong",
+    }
+}
+
+/// Coarse "5m ago" style rendering of a Unix timestamp.
+fn time_ago(now: u64, then: u64) -> String {
+    let seconds = now.saturating_sub(then);
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+/// Shows how the last answer was graded; returns true when "Next" is pressed.
+fn render_feedback(ui: &mut egui::Ui, outcome: &Outcome) -> bool {
+    let color = match outcome.verdict {
+        Verdict::Correct => egui::Color32::from_rgb(46, 160, 67),
+        Verdict::Partial => egui::Color32::from_rgb(210, 153, 34),
+        Verdict::Wrong => egui::Color32::from_rgb(218, 54, 51),
+    };
+    ui.label(
+        RichText::new(format!("{} {:+} points", outcome.verdict.symbol(), outcome.points))
+            .size(24.0)
+            .color(color),
+    );
+    ui.label(format!("Answer: {}", outcome.correct_answer));
+    if !outcome.marks.is_empty() {
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            ui.label("Yours:  ");
+            for mark in &outcome.marks {
+                let color = if mark.is_correct() {
+                    egui::Color32::from_rgb(46, 160, 67)
+                } else {
+                    egui::Color32::from_rgb(218, 54, 51)
+                };
+                ui.label(RichText::new(mark.shown().to_string()).monospace().color(color));
+            }
+        });
+    }
+    if let Some(explanation) = &outcome.explanation {
+        ui.label(RichText::new(explanation).italics());
+    }
+    ui.label(format!("Time: {:.1}s", outcome.elapsed.as_secs_f32()));
+    ui.button("Next ▶").clicked()
+}
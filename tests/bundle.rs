@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use memorykata::games::{Answer, GameState, Outcome, Question, QuestionKind, Verdict};
+use memorykata::storage::{
+    AttemptRecord, Bundle, ImportMode, SaveData, Storage, StorageBackend, StorageError,
+};
+
+fn progress(score: i32, attempts: i32, answered_at: &[u64]) -> (Storage, SaveData) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut storage = Storage::new(dir.path().join("save.json"));
+    let mut data = SaveData::default();
+    data.games.insert(
+        "math_tricks".into(),
+        GameState {
+            score,
+            attempts,
+            ..Default::default()
+        },
+    );
+
+    let question = Question::new("Expression: 2² + 1", QuestionKind::Number, vec!["5".into()]);
+    for &at in answered_at {
+        let answer = Answer::new("5", Duration::from_secs(1));
+        let outcome = Outcome::graded(Verdict::Correct, 3, &question, &answer);
+        let record = AttemptRecord::new("math_tricks", &question, &answer, &outcome, at);
+        storage.record_attempt(&mut data, record).unwrap();
+    }
+    (storage, data)
+}
+
+fn exported(storage: &Storage, data: &SaveData) -> Bundle {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bundle.json");
+    Bundle::export(storage, data, 99)
+        .unwrap()
+        .write(&path)
+        .unwrap();
+    Bundle::read(&path).unwrap()
+}
+
+#[test]
+fn export_round_trips_with_preview() {
+    let (storage, data) = progress(9, 3, &[1, 2, 3]);
+    let preview = exported(&storage, &data).preview();
+    assert_eq!(preview.exported_at, 99);
+    assert_eq!(preview.games, 1);
+    assert_eq!((preview.total_score, preview.total_attempts), (9, 3));
+    assert_eq!(preview.history_entries, 3);
+}
+
+#[test]
+fn replace_discards_current_progress() {
+    let (source, source_data) = progress(9, 3, &[1, 2, 3]);
+    let (mut storage, mut data) = progress(1, 1, &[10]);
+    data.settings.audio_enabled = false;
+
+    exported(&source, &source_data)
+        .import(&mut storage, &mut data, ImportMode::Replace)
+        .unwrap();
+    assert_eq!(data.games["math_tricks"].score, 9);
+    assert!(data.settings.audio_enabled);
+    assert_eq!(data.history.len(), 3);
+}
+
+#[test]
+fn merge_keeps_settings_and_deduplicates_history() {
+    let (source, source_data) = progress(9, 3, &[1, 2, 3]);
+    let (mut storage, mut data) = progress(1, 1, &[2, 10]);
+    data.settings.audio_enabled = false;
+
+    exported(&source, &source_data)
+        .import(&mut storage, &mut data, ImportMode::Merge)
+        .unwrap();
+    assert_eq!(data.games["math_tricks"].attempts, 3);
+    assert!(!data.settings.audio_enabled);
+    let times: Vec<u64> = data
+        .history
+        .iter()
+        .map(|record| record.answered_at)
+        .collect();
+    assert_eq!(times, [1, 2, 3, 10]);
+}
+
+#[test]
+fn merge_keeps_entries_and_peaks_from_both_sides() {
+    let (source, mut source_data) = progress(9, 3, &[]);
+    let (mut storage, mut data) = progress(1, 1, &[]);
+    let incoming = source_data.games.get_mut("math_tricks").unwrap();
+    incoming.entries.insert("peg.42".into(), "rain".into());
+    incoming.counters.insert("frontier.e".into(), 20);
+    incoming.counters.insert("best_streak".into(), 2);
+    let current = data.games.get_mut("math_tricks").unwrap();
+    current.entries.insert("peg.42".into(), "Rhine".into());
+    current.entries.insert("custom.card".into(), "4111".into());
+    current.counters.insert("frontier.e".into(), 35);
+    current.counters.insert("best_streak".into(), 5);
+    current.counters.insert("chunk".into(), 8);
+
+    exported(&source, &source_data)
+        .import(&mut storage, &mut data, ImportMode::Merge)
+        .unwrap();
+    let merged = &data.games["math_tricks"];
+    assert_eq!(merged.attempts, 3);
+    assert_eq!(merged.entries["peg.42"], "rain");
+    assert_eq!(merged.entries["custom.card"], "4111");
+    assert_eq!(merged.counters["frontier.e"], 35);
+    assert_eq!(merged.counters["best_streak"], 5);
+    assert!(!merged.counters.contains_key("chunk"));
+}
+
+#[test]
+fn other_json_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("save.json");
+    Storage::new(path.clone())
+        .save(&SaveData::default())
+        .unwrap();
+    assert!(matches!(
+        Bundle::read(&path),
+        Err(StorageError::NotABundle(_))
+    ));
+}
@@ -0,0 +1,87 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use memorykata::cli::{self, Cli, Command, Options, PlayOptions, DEFAULT_ROUNDS};
+use memorykata::stats::Format;
+use memorykata::storage::Profiles;
+
+fn parse(args: &[&str]) -> Result<Cli, cli::CliError> {
+    cli::parse(args.iter().map(OsString::from))
+}
+
+#[test]
+fn data_dir_flag_is_parsed_in_both_forms() {
+    for args in [&["--data-dir", "/tmp/kata"][..], &["--data-dir=/tmp/kata"]] {
+        let cli = parse(args).unwrap();
+        assert_eq!(cli.command, Command::Window);
+        assert_eq!(cli.options.data_dir(), PathBuf::from("/tmp/kata"));
+    }
+}
+
+#[test]
+fn play_takes_a_game_and_options_in_any_order() {
+    let cli = parse(&["play", "--seed=7", "math_tricks", "--profile", "Ada"]).unwrap();
+    assert_eq!(cli.options.profile.as_deref(), Some("Ada"));
+    assert_eq!(cli.options.seed, Some(7));
+    assert_eq!(
+        cli.command,
+        Command::Play(PlayOptions {
+            game: "math_tricks".into(),
+            rounds: DEFAULT_ROUNDS,
+        })
+    );
+
+    let Command::Play(options) = parse(&["--rounds", "3", "play", "pi_challenge"])
+        .unwrap()
+        .command
+    else {
+        panic!("not a play command");
+    };
+    assert_eq!(options.rounds, 3);
+}
+
+#[test]
+fn bad_arguments_are_rejected() {
+    assert_eq!(parse(&["--help"]).unwrap().command, Command::Help);
+    assert_eq!(
+        parse(&["stats", "--format=csv"]).unwrap().command,
+        Command::Stats(Format::Csv)
+    );
+    for args in [
+        &["--data-dir"][..],
+        &["--frobnicate"],
+        &["play"],
+        &["play", "math_tricks", "--rounds", "many"],
+        &["--rounds", "1"],
+        &["tui", "--seed", "many"],
+        &["dance"],
+        &["stats", "--format", "xml"],
+        &["play", "pi_challenge", "--format", "json"],
+    ] {
+        assert!(parse(args).is_err(), "{:?} was accepted", args);
+    }
+}
+
+#[test]
+fn default_data_dir_is_used_without_overrides() {
+    let options = Options::default();
+    if std::env::var_os(cli::DATA_DIR_ENV).is_none() {
+        assert_eq!(options.data_dir(), cli::default_data_dir());
+    }
+}
+
+#[test]
+fn profile_flag_is_not_remembered() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = Options {
+        data_dir: Some(dir.path().to_path_buf()),
+        profile: Some("ada".into()),
+        ..Options::default()
+    };
+    let mut profiles = Profiles::open(dir.path().to_path_buf(), options.save_file()).unwrap();
+    profiles.create("Ada").unwrap();
+    assert_eq!(options.profiles().unwrap().current().name, "Ada");
+
+    let reopened = Profiles::open(dir.path().to_path_buf(), options.save_file()).unwrap();
+    assert_eq!(reopened.current().name, "Default");
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use memorykata::daily::{self, DailyChallenge, DailyProgress, DAILY_ROUNDS};
+use memorykata::games::Answer;
+use memorykata::storage::Profiles;
+use memorykata::ui::App;
+
+/// Plays `rounds` rounds of `day` with the same wrong answer, returning the prompts.
+fn play(day: u64, progress: &mut DailyProgress, rounds: usize) -> Vec<String> {
+    let mut challenge = DailyChallenge::new(day, progress);
+    (0..rounds)
+        .map(|_| {
+            let prompt = challenge.question().prompt.clone();
+            challenge.check_answer(&Answer::new("?", Duration::from_secs(1)), progress);
+            challenge.next_round();
+            prompt
+        })
+        .collect()
+}
+
+#[test]
+fn a_day_has_a_fixed_set_of_rounds() {
+    let day = daily::day_of(1_700_000_000);
+    let first = play(day, &mut DailyProgress::default(), DAILY_ROUNDS);
+    let again = play(day, &mut DailyProgress::default(), DAILY_ROUNDS);
+    assert_eq!(first, again);
+    assert_ne!(
+        first,
+        play(day + 1, &mut DailyProgress::default(), DAILY_ROUNDS)
+    );
+
+    // Leaving halfway and coming back continues with the same rounds.
+    let mut progress = DailyProgress::default();
+    let mut resumed = play(day, &mut progress, 4);
+    resumed.extend(play(day, &mut progress, DAILY_ROUNDS - 4));
+    assert_eq!(first, resumed);
+    assert!(progress.result(day).unwrap().is_complete());
+    assert_eq!(progress.results.len(), 1);
+}
+
+#[test]
+fn streak_counts_consecutive_days() {
+    let mut progress = DailyProgress::default();
+    for day in [100, 101, 102, 104, 105] {
+        play(day, &mut progress, DAILY_ROUNDS);
+    }
+    assert_eq!(progress.streak, 2);
+    assert_eq!(progress.best_streak, 3);
+    assert_eq!(progress.current_streak(106), 2);
+    assert_eq!(progress.current_streak(107), 0);
+
+    // A day only counts once it is finished.
+    play(106, &mut progress, 3);
+    assert_eq!(progress.current_streak(106), 2);
+    assert_eq!(progress.result(106).unwrap().answered, 3);
+}
+
+#[test]
+fn dates_are_iso() {
+    assert_eq!(daily::date(0), "1970-01-01");
+    assert_eq!(daily::date(daily::day_of(1_710_374_400)), "2024-03-14");
+}
+
+#[test]
+fn daily_answers_are_kept_in_the_history() {
+    let dir = tempfile::tempdir().unwrap();
+    let profiles = Profiles::open(dir.path().to_path_buf(), "save.json").unwrap();
+    let storage = profiles.open_storage(&profiles.current().id).unwrap();
+    let mut app = App::new(profiles, storage, None);
+
+    app.start_daily();
+    app.answer_daily("?".into());
+
+    let result = &app.data.daily.results[0];
+    assert_eq!(result.answered, 1);
+    let mut challenge = DailyChallenge::new(result.day, &DailyProgress::default());
+    let asked = challenge.question().prompt.clone();
+    assert_eq!(app.data.history.len(), 1);
+    let record = &app.data.history[0];
+    assert_eq!(record.game, challenge.game().id());
+    assert_eq!(record.prompt, asked);
+    assert_eq!(record.given, "?");
+
+    let saved = app.storage.load().unwrap();
+    assert_eq!(saved.history, app.data.history);
+}
@@ -0,0 +1,45 @@
+use memorykata::digits::{self, Constant, DigitCache};
+
+#[test]
+fn pi_matches_the_reference_positions() {
+    let pi = Constant::Pi.digits(2000);
+    assert_eq!(pi.len(), 2000);
+    assert!(Constant::Pi.verify(&pi));
+    assert_eq!(&pi[762..768], "999999");
+    // Decimals 991–1000.
+    assert_eq!(&pi[991..1001], "2164201989");
+    assert!(!Constant::Pi.verify("31415926535897932384626433832795028841971693993752"));
+}
+
+#[test]
+fn every_constant_matches_its_references() {
+    for constant in Constant::ALL {
+        let digits = constant.digits(digits::BLOCK);
+        assert!(constant.verify(&digits), "{} is wrong", constant.symbol());
+    }
+    assert!(Constant::Ln2.digits(5) == "69314");
+}
+
+#[test]
+fn computed_digits_are_cached_on_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("pi.txt");
+    let cache = DigitCache::new(Some(dir.path().to_path_buf()));
+    let pi = cache.digits(Constant::Pi, 3 * digits::BLOCK);
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), pi);
+
+    // Going past what is known at least doubles it.
+    let more = cache.digits(Constant::Pi, 3 * digits::BLOCK + 1);
+    assert!(more.starts_with(&pi));
+    assert_eq!(
+        std::fs::read_to_string(&file).unwrap().len(),
+        6 * digits::BLOCK
+    );
+
+    // A new cache over the same directory starts from the file.
+    let reopened = DigitCache::new(Some(dir.path().to_path_buf()));
+    assert_eq!(
+        reopened.digits(Constant::Pi, 5 * digits::BLOCK)[..3001],
+        more
+    );
+}
@@ -0,0 +1,465 @@
+use memorykata::digits::Constant;
+use memorykata::games::constant_challenge::ConstantChallenge;
+use memorykata::games::crypto_puzzle::CryptoPuzzle;
+use memorykata::games::greek_alphabet::{self, GreekAlphabet};
+use memorykata::games::major_system::{self, Drill, MajorSystem, Mapping, Pegs};
+use memorykata::games::math_tricks::MathTricks;
+use memorykata::games::music_tempo::MusicTempo;
+use memorykata::games::pattern_memory::PatternMemory;
+use memorykata::games::periodic_table::PeriodicTable;
+use memorykata::games::physics_formulas::PhysicsFormulas;
+use memorykata::games::pi_challenge::PiChallenge;
+use memorykata::games::{self, Answer, GameEngine, RecallMode, Verdict};
+use memorykata::scheduler::Clock;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+fn all_games() -> Vec<Box<dyn GameEngine>> {
+    vec![
+        Box::new(PiChallenge::new()),
+        Box::new(GreekAlphabet::new()),
+        Box::new(MusicTempo::new()),
+        Box::new(MathTricks::new()),
+        Box::new(PhysicsFormulas::new()),
+        Box::new(PeriodicTable::new()),
+        Box::new(CryptoPuzzle::new()),
+        Box::new(PatternMemory::new()),
+        Box::new(ConstantChallenge::new()),
+        Box::new(MajorSystem::new()),
+    ]
+}
+
+fn prompts(game: &mut dyn GameEngine, rounds: usize) -> Vec<String> {
+    (0..rounds)
+        .map(|_| {
+            let prompt = game.question().prompt.clone();
+            game.next_round();
+            prompt
+        })
+        .collect()
+}
+
+fn answer(text: &str) -> Answer {
+    Answer::new(text, Duration::from_secs(1))
+}
+
+#[test]
+fn games_run_without_a_window() {
+    for mut game in all_games() {
+        assert!(
+            !game.question().prompt.is_empty(),
+            "{} has no prompt",
+            game.name()
+        );
+        let outcome = game.check_answer(&answer("definitely wrong"));
+        assert_eq!(
+            outcome.verdict,
+            Verdict::Wrong,
+            "{} accepted nonsense",
+            game.name()
+        );
+        assert_eq!(outcome.elapsed, Duration::from_secs(1));
+        assert_eq!(game.state().attempts, 1);
+        assert_eq!(game.state().score, outcome.points);
+    }
+}
+
+#[test]
+fn solution_is_always_accepted() {
+    for mut game in all_games() {
+        let solution = game.question().solution().to_string();
+        let outcome = game.check_answer(&answer(&solution));
+        assert!(
+            outcome.is_correct(),
+            "{} rejected {solution:?}",
+            game.name()
+        );
+        assert_eq!(outcome.correct_answer, solution);
+        assert!(outcome.points > 0);
+    }
+}
+
+#[test]
+fn pi_challenge_scores_correct_prefix() {
+    let mut game = PiChallenge::new();
+    assert_eq!(game.check_answer(&answer("31415")).points, 5);
+
+    let wrong = game.check_answer(&answer("31416"));
+    assert_eq!(wrong.points, -1);
+    assert_eq!(wrong.correct_answer, "31415");
+    assert_eq!(game.state().score, 4);
+    assert_eq!(game.state().attempts, 2);
+}
+
+#[test]
+fn pattern_memory_grows_on_success() {
+    let mut game = PatternMemory::new();
+    game.reseed(3);
+    let grown = |game: &PatternMemory| -> u32 {
+        game.state()
+            .counters
+            .values()
+            .map(|length| length - 3)
+            .sum()
+    };
+
+    // Typed keys are accepted as well as the symbols themselves.
+    for _ in 0..6 {
+        let keys = game.question().accepted[1].clone();
+        assert!(game.check_answer(&answer(&keys)).is_correct());
+        game.next_round();
+    }
+    assert_eq!(grown(&game), 6);
+
+    // A miss shortens the sequence again, never below three.
+    let alphabet = game
+        .question()
+        .prompt
+        .rsplit(' ')
+        .next()
+        .unwrap()
+        .to_string();
+    let length = |game: &PatternMemory| {
+        let key = format!("length.{}", alphabet);
+        game.state().counters.get(&key).copied().unwrap_or(3)
+    };
+    let before = length(&game);
+    assert_eq!(game.check_answer(&answer("?")).verdict, Verdict::Wrong);
+    assert_eq!(length(&game), (before - 1).max(3));
+}
+
+#[test]
+fn pi_challenge_advances_the_frontier() {
+    let mut game = PiChallenge::new();
+    assert_eq!(game.question().study.as_ref().unwrap().content, "31415");
+
+    game.check_answer(&answer("31415"));
+    game.next_round();
+    assert_eq!(game.frontier(), 5);
+    assert_eq!(game.question().solution(), "92653");
+    assert!(game.question().prompt.contains("…31415"));
+
+    game.check_answer(&answer("92654"));
+    game.next_round();
+    assert_eq!(game.frontier(), 5);
+    assert_eq!(game.question().solution(), "92653");
+}
+
+#[test]
+fn pi_challenge_practises_a_range() {
+    let mut game = PiChallenge::new();
+    game.set_range(101, 110);
+    game.set_mode(RecallMode::Range);
+    assert_eq!(game.question().solution(), "98214");
+    assert!(game.question().prompt.contains("type digits 101–105"));
+
+    let outcome = game.check_answer(&answer("98314"));
+    assert!(!outcome.is_correct());
+    let wrong: Vec<_> = outcome.marks.iter().map(|mark| mark.is_correct()).collect();
+    assert_eq!(wrong, [true, true, false, true, true]);
+    game.next_round();
+    assert_eq!(game.question().solution(), "98214");
+
+    game.check_answer(&answer("98214"));
+    game.next_round();
+    assert_eq!(game.question().solution(), "80865");
+    game.check_answer(&answer("80865"));
+    game.next_round();
+    assert_eq!(game.question().solution(), "98214");
+    assert_eq!(game.frontier(), 0);
+}
+
+#[test]
+fn pi_challenge_quizzes_positions() {
+    let pi = Constant::Pi.digits(100);
+    let mut game = PiChallenge::new();
+    game.set_rng(games::seeded_rng(3));
+    game.set_mode(RecallMode::DigitsAt);
+    for _ in 0..10 {
+        let item = game.question().item.clone().unwrap();
+        let (from, to) = item["pi:".len()..].split_once('-').unwrap();
+        let (from, to): (usize, usize) = (from.parse().unwrap(), to.parse().unwrap());
+        assert!((1..=96).contains(&from));
+        assert_eq!(game.question().solution(), &pi[from - 1..to]);
+        assert!(game.question().study.is_none());
+        game.next_round();
+    }
+
+    game.set_mode(RecallMode::BlockStart);
+    for _ in 0..10 {
+        let position: usize = game.question().solution().parse().unwrap();
+        assert!(game
+            .question()
+            .prompt
+            .contains(&pi[position - 1..position + 5]));
+        let outcome = game.check_answer(&answer(&position.to_string()));
+        assert_eq!((outcome.verdict, outcome.points), (Verdict::Correct, 3));
+        game.next_round();
+    }
+    assert_eq!(game.frontier(), 0);
+}
+
+#[test]
+fn constant_challenge_keeps_a_frontier_per_sequence() {
+    let mut game = ConstantChallenge::new();
+    assert_eq!(game.question().solution(), "27182");
+    game.check_answer(&answer("27182"));
+    game.next_round();
+    assert_eq!(game.question().solution(), "81828");
+
+    assert!(game.select("ln2"));
+    assert_eq!(game.question().solution(), "69314");
+    assert!(game.select("e"));
+    assert_eq!(game.question().solution(), "81828");
+
+    assert!(game.add_custom("Phone", "555-0123").is_ok());
+    assert_eq!(game.question().prompt, "Type the first 5 digits of Phone");
+    game.check_answer(&answer("55501"));
+    game.next_round();
+    assert_eq!(game.question().solution(), "23");
+    game.check_answer(&answer("23"));
+    game.next_round();
+    assert_eq!(game.question().prompt, "Type all of Phone");
+    assert_eq!(game.state().counters["best_streak.custom.Phone"], 2);
+
+    assert!(game.add_custom("Phone", "1234").is_err());
+    assert!(game.add_custom("Card", "12ab").is_err());
+    game.remove_custom("Phone");
+    assert_eq!(game.question().solution(), "81828");
+}
+
+#[test]
+fn round_stays_until_next_round() {
+    for mut game in all_games() {
+        let prompt = game.question().prompt.clone();
+        let outcome = game.check_answer(&answer("definitely wrong"));
+        assert_eq!(
+            game.question().prompt,
+            prompt,
+            "{} moved on early",
+            game.name()
+        );
+        assert!(!outcome.correct_answer.is_empty());
+        assert!(
+            outcome.explanation.is_some(),
+            "{} gave no explanation",
+            game.name()
+        );
+        game.next_round();
+    }
+}
+
+#[test]
+fn game_ids_are_unique() {
+    let mut ids: Vec<&str> = all_games().iter().map(|game| game.id()).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), all_games().len());
+}
+
+/// Prompts of `rounds` rounds, answering every other one correctly.
+fn answered_prompts(game: &mut dyn GameEngine, rounds: usize) -> Vec<String> {
+    (0..rounds)
+        .map(|round| {
+            let question = game.question().clone();
+            let text = match round % 2 {
+                0 => question.accepted[0].as_str(),
+                _ => "definitely wrong",
+            };
+            game.check_answer(&answer(text));
+            game.next_round();
+            question.prompt
+        })
+        .collect()
+}
+
+#[test]
+fn seed_reproduces_the_rounds() {
+    for (mut first, mut second) in all_games().into_iter().zip(all_games()) {
+        first.reseed(7);
+        second.reseed(7);
+        assert_eq!(
+            prompts(first.as_mut(), 20),
+            prompts(second.as_mut(), 20),
+            "{} is not reproducible",
+            first.name()
+        );
+    }
+    for (mut first, mut second) in all_games().into_iter().zip(all_games()) {
+        first.set_clock(Clock::Fixed(1_700_000_000));
+        second.set_clock(Clock::Fixed(1_700_000_000));
+        first.reseed(7);
+        second.reseed(7);
+        assert_eq!(
+            answered_prompts(first.as_mut(), 20),
+            answered_prompts(second.as_mut(), 20),
+            "{} is not reproducible once answered",
+            first.name()
+        );
+    }
+}
+
+#[test]
+fn different_seeds_give_different_rounds() {
+    let mut first = MathTricks::new();
+    let mut second = MathTricks::new();
+    first.reseed(1);
+    second.reseed(2);
+    assert_ne!(prompts(&mut first, 20), prompts(&mut second, 20));
+}
+
+#[test]
+fn major_system_pegs_spell_their_numbers() {
+    let mapping = Mapping::default();
+    for (number, peg) in major_system::PEGS.iter().enumerate() {
+        assert_eq!(mapping.digits(peg), format!("{:02}", number), "{}", peg);
+    }
+    for (digit, peg) in major_system::DIGIT_PEGS.iter().enumerate() {
+        assert_eq!(mapping.digits(peg), digit.to_string(), "{}", peg);
+    }
+    assert_eq!(mapping.digits("bell lip"), "9559");
+    assert_eq!(Pegs::default().mnemonic("31415"), "31 mat · 41 rat · 5 law");
+}
+
+#[test]
+fn major_system_grades_words_by_their_sounds() {
+    let mut game = MajorSystem::new();
+    game.reseed(5);
+    let number: usize = game.question().item.clone().unwrap().parse().unwrap();
+    let peg = game.peg(number).unwrap();
+    assert_eq!(game.question().solution(), peg);
+    assert!(game.check_answer(&answer(&peg)).is_correct());
+
+    assert!(game.set_peg(31, "meat").is_ok());
+    assert!(game.set_peg(31, "moon").is_err());
+    assert_eq!(game.peg(31).as_deref(), Some("meat"));
+    assert!(game.set_sounds(0, "s").is_ok());
+    assert_eq!(game.mapping().digits("zoos"), "0");
+    assert!(game.set_sounds(1, "s").is_err());
+
+    game.set_drill(Drill::Numbers);
+    let number = game
+        .question()
+        .prompt
+        .split(' ')
+        .nth(1)
+        .unwrap()
+        .to_string();
+    let spelled = game.mapping().digits(game.question().solution());
+    assert_eq!(spelled, number);
+    let outcome = game.check_answer(&answer("xyz"));
+    assert_eq!(outcome.verdict, Verdict::Wrong);
+    assert!(outcome
+        .explanation
+        .unwrap()
+        .starts_with("“xyz” spells nothing"));
+}
+
+#[test]
+fn pi_challenge_shows_pegs_while_memorising() {
+    let mut game = PiChallenge::new();
+    assert_eq!(game.question().study.as_ref().unwrap().note, None);
+    game.set_mnemonics(true);
+    game.next_round();
+    let study = game.question().study.clone().unwrap();
+    assert_eq!(study.note.as_deref(), Some("31 mat · 41 rat · 5 law"));
+}
+
+#[test]
+fn pegs_follow_the_players_sounds() {
+    let mut system = MajorSystem::new();
+    system.set_peg(31, "meat").unwrap();
+    system.set_peg(41, "rut").unwrap();
+    let mut sounds = major_system::DEFAULT_SOUNDS.map(String::from);
+    sounds[2] = "n m".into();
+    sounds[3] = "w".into();
+    assert_eq!(system.set_mapping(&sounds), Ok(1));
+    assert_eq!(system.peg(31), None);
+    assert_eq!(system.peg(21).as_deref(), Some("net"));
+    assert_eq!(system.peg(41).as_deref(), Some("rut"));
+
+    system.set_drill(Drill::Words);
+    for _ in 0..100 {
+        let word = system.question().item.clone().unwrap();
+        let number = system.question().solution().to_string();
+        assert_eq!(system.mapping().digits(&word), number, "{}", word);
+        system.check_answer(&answer(&number));
+        system.next_round();
+    }
+
+    let mut games = BTreeMap::new();
+    games.insert(major_system::ID.to_string(), system.state().clone());
+    let mut pi = PiChallenge::new();
+    pi.set_mnemonics(true);
+    pi.next_round();
+    pi.share_progress(&games);
+    let study = pi.question().study.clone().unwrap();
+    assert_eq!(study.note.as_deref(), Some("31 w-t/d · 41 rut · 5 l"));
+}
+
+#[test]
+fn greek_alphabet_covers_every_letter_in_every_mode() {
+    let mut game = GreekAlphabet::new();
+    game.reseed(11);
+    let mut names = std::collections::BTreeSet::new();
+    for _ in 0..52 {
+        let symbol = game.question().item.clone().unwrap();
+        assert!(game
+            .check_answer(&answer(game.question().solution()))
+            .is_correct());
+        names.insert(symbol);
+        game.next_round();
+    }
+    assert_eq!(
+        names.len(),
+        52,
+        "24 capitals, 24 small letters and 4 variants"
+    );
+    assert!(names.contains("ς") && names.contains("ϑ"));
+
+    game.set_mode(greek_alphabet::Mode::Symbols);
+    let solution = game.question().solution().to_string();
+    assert!(
+        game.question().prompt.starts_with("Capital ")
+            || game.question().prompt.starts_with("Small ")
+    );
+    assert!(game.check_answer(&answer(&solution)).is_correct());
+
+    game.set_mode(greek_alphabet::Mode::Cases);
+    for _ in 0..20 {
+        let prompt = game.question().prompt.clone();
+        let shown = prompt.rsplit(' ').next().unwrap();
+        let expected = if prompt.starts_with("Small") {
+            shown.to_lowercase()
+        } else if shown == "ς" {
+            "Σ".to_string()
+        } else {
+            shown.to_uppercase()
+        };
+        assert!(game.question().accepts(&expected), "{}", prompt);
+        game.next_round();
+    }
+
+    game.set_mode(greek_alphabet::Mode::Words);
+    assert!(game
+        .question()
+        .prompt
+        .starts_with("Write in Latin letters: "));
+}
+
+#[test]
+fn greek_words_transliterate_letter_by_letter() {
+    assert_eq!(greek_alphabet::transliterate("λόγος"), "logos");
+    assert_eq!(greek_alphabet::transliterate("ψυχή"), "psyche");
+    assert_eq!(greek_alphabet::transliterate("αυτός"), "autos");
+    assert_eq!(greek_alphabet::transliterate("Θεός"), "theos");
+
+    let mut game = GreekAlphabet::new();
+    game.set_mode(greek_alphabet::Mode::Words);
+    while !game.question().prompt.ends_with("χάος") {
+        game.check_answer(&answer("chaos"));
+        game.next_round();
+    }
+    assert!(game.check_answer(&answer("Khaos")).is_correct());
+}
@@ -0,0 +1,94 @@
+use std::io::Cursor;
+
+use memorykata::cli::PlayOptions;
+use memorykata::headless::{self, PlayError};
+use memorykata::storage::{SaveData, Storage};
+
+#[test]
+fn answers_are_graded_and_saved() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut storage = Storage::new(dir.path().join("save.json"));
+    // Keep each chunk on screen for just a second.
+    let mut data = SaveData::default();
+    let pi = data.games.entry("pi_challenge".into()).or_default();
+    pi.counters.insert("study_seconds".into(), 1);
+    storage.save(&data).unwrap();
+
+    let options = PlayOptions {
+        game: "pi_challenge".into(),
+        rounds: 3,
+    };
+    let input = "hint\n31415\n\n9\nquit\n";
+    let mut output = Vec::new();
+    let summary = headless::play(
+        &mut storage,
+        &options,
+        None,
+        &mut Cursor::new(input),
+        &mut output,
+        false,
+    )
+    .unwrap();
+
+    assert_eq!((summary.rounds, summary.correct, summary.points), (2, 1, 4));
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Memorise (1.0s): 92653"), "{}", output);
+    assert!(output.contains("\n(hidden)\n"), "{}", output);
+    assert!(!output.contains('\x1b'), "{}", output);
+    let data = storage.load().unwrap();
+    assert_eq!(data.games["pi_challenge"].attempts, 2);
+    assert_eq!(data.games["pi_challenge"].counters["frontier"], 5);
+    assert_eq!(data.history.len(), 2);
+}
+
+#[test]
+fn seeded_sessions_ask_the_same_rounds() {
+    let prompts = |dir: &tempfile::TempDir| {
+        let mut storage = Storage::new(dir.path().join("save.json"));
+        let options = PlayOptions {
+            game: "math_tricks".into(),
+            rounds: 5,
+        };
+        let mut output = Vec::new();
+        let input = "0\n".repeat(5);
+        headless::play(
+            &mut storage,
+            &options,
+            Some(42),
+            &mut Cursor::new(input),
+            &mut output,
+            false,
+        )
+        .unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .filter(|line| line.starts_with('['))
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    let first = prompts(&tempfile::tempdir().unwrap());
+    assert_eq!(first.len(), 5);
+    assert_eq!(first, prompts(&tempfile::tempdir().unwrap()));
+}
+
+#[test]
+fn unknown_games_list_the_choices() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut storage = Storage::new(dir.path().join("save.json"));
+    let options = PlayOptions {
+        game: "chess".into(),
+        rounds: 1,
+    };
+    let err = headless::play(
+        &mut storage,
+        &options,
+        None,
+        &mut Cursor::new(""),
+        &mut Vec::new(),
+        false,
+    )
+    .expect_err("chess is not a kata");
+    assert!(matches!(err, PlayError::UnknownGame { .. }));
+    assert!(err.to_string().contains("greek_alphabet"), "{}", err);
+}
@@ -0,0 +1,97 @@
+use std::fs;
+
+use memorykata::games::GameState;
+use memorykata::storage::{Profiles, SaveData, StorageError};
+use memorykata::ui::App;
+
+#[test]
+fn first_start_adopts_existing_save() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("save.json"), "{}").unwrap();
+    fs::write(dir.path().join("save.json.1"), "{}").unwrap();
+
+    let profiles = Profiles::open(dir.path().to_path_buf(), "save.json").unwrap();
+    assert_eq!(profiles.list().len(), 1);
+    let current = profiles.current();
+    assert_eq!(current.name, "Default");
+    assert!(profiles.save_path(&current.id).exists());
+    assert!(!dir.path().join("save.json").exists());
+    assert!(!dir.path().join("save.json.1").exists());
+}
+
+#[test]
+fn profiles_and_last_used_survive_a_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut profiles = Profiles::open(dir.path().to_path_buf(), "save.json").unwrap();
+    let ada = profiles.create("Ada").unwrap();
+    let bob = profiles.create("Bob").unwrap();
+    profiles.rename(&bob.id, "Bobby").unwrap();
+    profiles.set_current(&ada.id).unwrap();
+    assert_ne!(profiles.save_path(&ada.id), profiles.save_path(&bob.id));
+
+    let profiles = Profiles::open(dir.path().to_path_buf(), "save.json").unwrap();
+    let names: Vec<&str> = profiles.list().iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["Default", "Ada", "Bobby"]);
+    assert_eq!(profiles.current().id, ada.id);
+}
+
+#[test]
+fn names_must_be_unique_and_current_profile_stays() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut profiles = Profiles::open(dir.path().to_path_buf(), "save.json").unwrap();
+    let ada = profiles.create("Ada").unwrap();
+    assert!(matches!(
+        profiles.create(" ada "),
+        Err(StorageError::InvalidProfileName(_))
+    ));
+    assert!(matches!(
+        profiles.create(""),
+        Err(StorageError::InvalidProfileName(_))
+    ));
+
+    profiles.set_current(&ada.id).unwrap();
+    assert!(matches!(
+        profiles.delete(&ada.id),
+        Err(StorageError::ProfileInUse(_))
+    ));
+
+    let default = profiles.list()[0].id.clone();
+    fs::write(profiles.save_path(&default), "{}").unwrap();
+    profiles.delete(&default).unwrap();
+    assert!(!profiles.save_path(&default).exists());
+    assert_eq!(profiles.list().len(), 1);
+}
+
+#[test]
+fn switching_to_an_unreadable_profile_keeps_progress_apart() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut profiles = Profiles::open(dir.path().to_path_buf(), "save.json").unwrap();
+    let ada = profiles.create("Ada").unwrap();
+    let kid = profiles.create("Kid").unwrap();
+    profiles.set_current(&ada.id).unwrap();
+    let mut data = SaveData::default();
+    let state = GameState {
+        score: 7,
+        attempts: 9,
+        ..GameState::default()
+    };
+    data.games.insert("greek_alphabet".into(), state);
+    profiles.open_storage(&ada.id).unwrap().save(&data).unwrap();
+    fs::write(profiles.save_path(&kid.id), "not json").unwrap();
+
+    let storage = profiles.open_storage(&ada.id).unwrap();
+    let mut app = App::new(profiles, storage, None);
+    assert_eq!(app.data.games["greek_alphabet"].score, 7);
+
+    app.switch_profile(&kid.id);
+    assert!(app.data.games.is_empty());
+    assert!(app.modules.iter().all(|module| module.state().score == 0));
+
+    // "Start fresh" on the load problem banner.
+    app.dismiss_storage_problem();
+    app.save();
+    let saved = app.profiles.open_storage(&kid.id).unwrap().load().unwrap();
+    assert!(saved.games.values().all(|state| state.score == 0));
+    let saved = app.profiles.open_storage(&ada.id).unwrap().load().unwrap();
+    assert_eq!(saved.games["greek_alphabet"].score, 7);
+}
@@ -3,5 +3,48 @@ use assert_cmd::Command;
 #[test]
 fn binary_runs() {
     let mut cmd = Command::cargo_bin("memorykata").expect("binary built");
-    cmd.assert().success();
+    cmd.arg("--help").assert().success();
+}
+
+#[test]
+fn plays_a_round_in_the_terminal() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("memorykata").expect("binary built");
+    let assert = cmd
+        .arg("--data-dir")
+        .arg(dir.path())
+        .args(["play", "pi_challenge", "--rounds", "1"])
+        .env_remove("MEMORYKATA_BACKEND")
+        .write_stdin("31415\n")
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout);
+    assert!(stdout.contains("✓ +5 points"), "{}", stdout);
+    assert!(dir.path().join("profiles/default/save.json").exists());
+}
+
+#[test]
+fn data_dir_comes_from_the_environment_unless_given() {
+    let from_env = tempfile::tempdir().unwrap();
+    let from_flag = tempfile::tempdir().unwrap();
+    let stats = |flag: Option<&std::path::Path>| {
+        let mut cmd = Command::cargo_bin("memorykata").expect("binary built");
+        if let Some(dir) = flag {
+            cmd.arg("--data-dir").arg(dir);
+        }
+        cmd.arg("stats")
+            .env("MEMORYKATA_DATA_DIR", from_env.path())
+            .env_remove("MEMORYKATA_BACKEND")
+            .assert()
+            .success();
+    };
+
+    stats(None);
+    assert!(from_env.path().join("profiles.json").exists());
+    assert!(!from_flag.path().join("profiles.json").exists());
+
+    std::fs::remove_file(from_env.path().join("profiles.json")).unwrap();
+    stats(Some(from_flag.path()));
+    assert!(from_flag.path().join("profiles.json").exists());
+    assert!(!from_env.path().join("profiles.json").exists());
 }
@@ -0,0 +1,84 @@
+#![cfg(feature = "sqlite")]
+
+use std::time::Duration;
+
+use memorykata::games::{Answer, Outcome, Question, QuestionKind, Verdict};
+use memorykata::storage::{
+    AttemptRecord, HistoryQuery, SaveData, SqliteStorage, StorageBackend, SCHEMA_VERSION,
+};
+
+fn attempt(game: &str, verdict: Verdict, answered_at: u64) -> AttemptRecord {
+    let question =
+        Question::new("Symbol: Ω", QuestionKind::Text, vec!["Omega".into()]).with_item("Ω");
+    let answer = Answer::new("Omega", Duration::from_millis(1500));
+    let outcome = Outcome::graded(verdict, 2, &question, &answer);
+    AttemptRecord::new(game, &question, &answer, &outcome, answered_at)
+}
+
+#[test]
+fn save_data_round_trips() {
+    let storage = SqliteStorage::in_memory().unwrap();
+    assert_eq!(storage.load().unwrap().version, SCHEMA_VERSION);
+
+    let mut data = SaveData::default();
+    data.settings.audio_enabled = false;
+    storage.save(&data).unwrap();
+    storage.save(&data).unwrap();
+    assert!(!storage.load().unwrap().settings.audio_enabled);
+}
+
+#[test]
+fn history_is_filtered_and_newest_first() {
+    let mut storage = SqliteStorage::in_memory().unwrap();
+    let mut data = SaveData::default();
+    storage
+        .record_attempt(&mut data, attempt("greek_alphabet", Verdict::Correct, 10))
+        .unwrap();
+    storage
+        .record_attempt(&mut data, attempt("greek_alphabet", Verdict::Wrong, 20))
+        .unwrap();
+    storage
+        .record_attempt(&mut data, attempt("periodic_table", Verdict::Wrong, 30))
+        .unwrap();
+
+    let all = storage.history(&data, &HistoryQuery::default()).unwrap();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].answered_at, 30);
+    assert_eq!(all[2], attempt("greek_alphabet", Verdict::Correct, 10));
+
+    let misses = HistoryQuery {
+        game: Some("greek_alphabet".into()),
+        verdict: Some(Verdict::Wrong),
+        limit: None,
+    };
+    let misses = storage.history(&data, &misses).unwrap();
+    assert_eq!(misses.len(), 1);
+    assert_eq!(misses[0].answered_at, 20);
+
+    let stats = storage.stats(&data, "greek_alphabet").unwrap();
+    assert_eq!(stats.attempts, 2);
+    assert_eq!(stats.correct, 1);
+    assert_eq!(stats.average_elapsed, Duration::from_millis(1500));
+}
+
+#[test]
+fn replacing_history_swaps_every_answer() {
+    let mut storage = SqliteStorage::in_memory().unwrap();
+    let mut data = SaveData::default();
+    storage
+        .record_attempt(&mut data, attempt("greek_alphabet", Verdict::Wrong, 10))
+        .unwrap();
+    let replacement = vec![
+        attempt("periodic_table", Verdict::Correct, 20),
+        attempt("periodic_table", Verdict::Wrong, 30),
+    ];
+    storage.replace_history(&mut data, replacement).unwrap();
+
+    let times: Vec<u64> = storage
+        .history(&data, &HistoryQuery::default())
+        .unwrap()
+        .iter()
+        .map(|record| record.answered_at)
+        .collect();
+    assert_eq!(times, [30, 20]);
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use memorykata::games::{self, Answer, Outcome, Question, QuestionKind, Verdict};
+use memorykata::stats::{self, Format, Report};
+use memorykata::storage::{AttemptRecord, SaveData, Storage, StorageBackend};
+
+fn record(verdict: Verdict, answered_at: u64) -> AttemptRecord {
+    let question = Question::new("Symbol: Δ", QuestionKind::Text, vec!["Delta".into()]);
+    let answer = Answer::new("Delta", Duration::from_secs(2));
+    let outcome = Outcome::graded(verdict, 2, &question, &answer);
+    AttemptRecord::new("greek_alphabet", &question, &answer, &outcome, answered_at)
+}
+
+#[test]
+fn streaks_count_runs_of_correct_answers() {
+    use Verdict::*;
+    assert_eq!(stats::streaks([]), (0, 0));
+    assert_eq!(
+        stats::streaks([Correct, Correct, Correct, Wrong, Correct, Partial, Correct]),
+        (1, 3)
+    );
+}
+
+#[test]
+fn report_covers_every_game_and_every_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut storage = Storage::new(dir.path().join("save.json"));
+    let mut data = SaveData::default();
+    for (at, verdict) in [Verdict::Wrong, Verdict::Correct, Verdict::Correct]
+        .into_iter()
+        .enumerate()
+    {
+        storage
+            .record_attempt(&mut data, record(verdict, at as u64))
+            .unwrap();
+    }
+    data.games
+        .entry("greek_alphabet".into())
+        .or_default()
+        .attempts = 3;
+
+    let report = Report::collect("Ada, Jr.", &storage, &data, 10).unwrap();
+    let known = games::all().len();
+    assert_eq!(report.games.len(), known);
+    let position = report
+        .games
+        .iter()
+        .position(|game| game.game == "greek_alphabet")
+        .unwrap();
+    let greek = &report.games[position];
+    assert_eq!(greek.attempts, 3);
+    assert_eq!((greek.current_streak, greek.best_streak), (2, 2));
+    assert!((greek.accuracy.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+
+    let render = |format| {
+        let mut output = Vec::new();
+        report.write(format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+    assert!(render(Format::Table).contains("Greek Alphabet"));
+    let json: serde_json::Value = serde_json::from_str(&render(Format::Json)).unwrap();
+    assert_eq!(json["games"][position]["best_streak"], 2);
+    let csv = render(Format::Csv);
+    // One line per game after the header.
+    assert_eq!(csv.lines().count(), known + 1);
+    assert!(csv.contains("\"Ada, Jr.\",greek_alphabet,Greek Alphabet,0,3,0.6667,2,2,0"));
+}
@@ -0,0 +1,213 @@
+use std::fs;
+
+use std::time::Duration;
+
+use memorykata::games::{Answer, GameState, Outcome, Question, QuestionKind, Verdict};
+use memorykata::storage::{
+    AttemptRecord, HistoryQuery, MigrationError, SaveData, Storage, StorageBackend, StorageError,
+    JSON_HISTORY_PER_GAME, SCHEMA_VERSION,
+};
+
+const V0_SAVE: &str = r#"{
+  "scores": [
+    { "score": 12, "attempts": 4, "description": "Memorize digits of π" }
+  ],
+  "settings": { "audio_enabled": false, "theme": "Dark" }
+}"#;
+
+fn storage_in(dir: &tempfile::TempDir) -> Storage {
+    Storage::new(dir.path().join("save.json"))
+}
+
+#[test]
+fn missing_file_loads_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let data = storage_in(&dir).load().unwrap();
+    assert_eq!(data.version, SCHEMA_VERSION);
+    assert!(data.games.is_empty());
+}
+
+#[test]
+fn unversioned_file_is_migrated_and_backed_up() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = storage_in(&dir);
+    fs::write(&storage.path, V0_SAVE).unwrap();
+
+    let data = storage.load().unwrap();
+    assert_eq!(data.version, SCHEMA_VERSION);
+    assert_eq!(data.games["pi_challenge"].score, 12);
+    assert!(!data.settings.audio_enabled);
+    assert_eq!(fs::read_to_string(storage.backup_path(0)).unwrap(), V0_SAVE);
+}
+
+#[test]
+fn newer_file_is_reported_not_reset() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = storage_in(&dir);
+    let future = format!(r#"{{ "version": {}, "games": {{}} }}"#, SCHEMA_VERSION + 1);
+    fs::write(&storage.path, &future).unwrap();
+
+    assert!(matches!(
+        storage.load(),
+        Err(StorageError::Migration(MigrationError::UnsupportedVersion(
+            _
+        )))
+    ));
+    assert_eq!(fs::read_to_string(&storage.path).unwrap(), future);
+}
+
+#[test]
+fn save_round_trips() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = storage_in(&dir);
+    let mut data = SaveData::default();
+    data.settings.audio_enabled = false;
+    storage.save(&data).unwrap();
+
+    let loaded = storage.load().unwrap();
+    assert_eq!(loaded.version, SCHEMA_VERSION);
+    assert!(!loaded.settings.audio_enabled);
+}
+
+#[test]
+fn saves_rotate_into_numbered_backups() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = storage_in(&dir).with_backups(2);
+    for score in 1..=4 {
+        let mut data = SaveData::default();
+        data.games.insert(
+            "pi_challenge".into(),
+            GameState {
+                score,
+                ..Default::default()
+            },
+        );
+        storage.save(&data).unwrap();
+    }
+
+    let score_in = |path| -> i32 {
+        let data: SaveData = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        data.games["pi_challenge"].score
+    };
+    assert_eq!(score_in(storage.path.clone()), 4);
+    assert_eq!(score_in(storage.rotated_path(1)), 3);
+    assert_eq!(score_in(storage.rotated_path(2)), 2);
+    assert!(!storage.rotated_path(3).exists());
+    assert!(!dir.path().join("save.json.tmp").exists());
+}
+
+#[test]
+fn corrupt_save_falls_back_to_newest_backup() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = storage_in(&dir);
+    let mut data = SaveData::default();
+    data.settings.audio_enabled = false;
+    storage.save(&data).unwrap();
+    storage.save(&data).unwrap();
+
+    fs::write(&storage.path, "{ truncated").unwrap();
+    let loaded = storage.load().unwrap();
+    assert!(!loaded.settings.audio_enabled);
+
+    fs::remove_file(&storage.path).unwrap();
+    assert!(!storage.load().unwrap().settings.audio_enabled);
+}
+
+#[test]
+fn write_failures_are_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let blocker = dir.path().join("not-a-dir");
+    fs::write(&blocker, "").unwrap();
+
+    let storage = Storage::new(blocker.join("save.json"));
+    assert!(matches!(
+        storage.save(&SaveData::default()),
+        Err(StorageError::Io { .. })
+    ));
+}
+
+#[test]
+fn positional_scores_are_keyed_by_game_id() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = storage_in(&dir);
+    let entry = |score| {
+        format!(
+            r#"{{ "score": {}, "attempts": 1, "description": "" }}"#,
+            score
+        )
+    };
+    let scores: Vec<String> = (0..9).map(entry).collect();
+    let v1 = format!(
+        r#"{{ "version": 1, "scores": [{}], "settings": {{ "audio_enabled": true, "theme": "Light" }} }}"#,
+        scores.join(",")
+    );
+    fs::write(&storage.path, v1).unwrap();
+
+    let data = storage.load().unwrap();
+    assert_eq!(data.games["greek_alphabet"].score, 1);
+    assert_eq!(data.games["crypto_puzzle"].score, 6);
+    // Entries past the known games survive instead of being dropped.
+    assert_eq!(data.games["unknown_8"].score, 8);
+}
+
+#[test]
+fn json_history_is_kept_in_the_save_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut storage = storage_in(&dir);
+    let mut data = SaveData::default();
+    let question = Question::new(
+        "Expression: 3² + 1",
+        QuestionKind::Number,
+        vec!["10".into()],
+    );
+    for (given, verdict, at) in [("10", Verdict::Correct, 1), ("11", Verdict::Wrong, 2)] {
+        let answer = Answer::new(given, Duration::from_secs(2));
+        let outcome = Outcome::graded(verdict, 1, &question, &answer);
+        let record = AttemptRecord::new("math_tricks", &question, &answer, &outcome, at);
+        StorageBackend::record_attempt(&mut storage, &mut data, record).unwrap();
+    }
+    storage.save(&data).unwrap();
+
+    let data = storage.load().unwrap();
+    let wrong = HistoryQuery {
+        verdict: Some(Verdict::Wrong),
+        ..Default::default()
+    };
+    let misses = storage.history(&data, &wrong).unwrap();
+    assert_eq!(misses.len(), 1);
+    assert_eq!(misses[0].given, "11");
+    assert_eq!(misses[0].expected, "10");
+
+    let stats = storage.stats(&data, "math_tricks").unwrap();
+    assert_eq!((stats.attempts, stats.correct), (2, 1));
+    assert_eq!(stats.accuracy(), 0.5);
+}
+
+#[test]
+fn json_history_keeps_the_newest_answers_of_each_game() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut storage = storage_in(&dir);
+    let mut data = SaveData::default();
+    let question = Question::new("Expression: 2 + 2", QuestionKind::Number, vec!["4".into()]);
+    let answer = Answer::new("4", Duration::from_secs(1));
+    let outcome = Outcome::graded(Verdict::Correct, 1, &question, &answer);
+    let greek = AttemptRecord::new("greek_alphabet", &question, &answer, &outcome, 0);
+    StorageBackend::record_attempt(&mut storage, &mut data, greek).unwrap();
+    for at in 1..=JSON_HISTORY_PER_GAME as u64 + 5 {
+        let record = AttemptRecord::new("math_tricks", &question, &answer, &outcome, at);
+        StorageBackend::record_attempt(&mut storage, &mut data, record).unwrap();
+    }
+
+    // A busy game does not push out the answers of a quiet one.
+    assert_eq!(data.history.len(), JSON_HISTORY_PER_GAME + 1);
+    assert_eq!(data.history[0].game, "greek_alphabet");
+    assert_eq!(data.history[1].answered_at, 6);
+    assert_eq!(data.trimmed_history["math_tricks"], 5);
+    assert!(!data.trimmed_history.contains_key("greek_alphabet"));
+
+    storage.save(&data).unwrap();
+    assert_eq!(
+        storage.load().unwrap().trimmed_history,
+        data.trimmed_history
+    );
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "tui")]
+
+use memorykata::storage::Storage;
+use memorykata::tui::{Screen, Tui};
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Terminal;
+
+fn press(tui: &mut Tui, keys: impl IntoIterator<Item = KeyCode>) {
+    for code in keys {
+        tui.handle_key(KeyEvent::from(code));
+    }
+}
+
+fn type_text(tui: &mut Tui, text: &str) {
+    press(tui, text.chars().map(KeyCode::Char));
+}
+
+fn screen_text(tui: &Tui) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    terminal.draw(|frame| tui.draw(frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer.content().iter().map(|cell| cell.symbol()).collect()
+}
+
+#[test]
+fn plays_a_round_and_saves_on_exit() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("save.json");
+    let mut tui = Tui::new(Box::new(Storage::new(path.clone())), None);
+    assert!(screen_text(&tui).contains("Π Challenge"));
+
+    press(&mut tui, [KeyCode::Enter]);
+    assert_eq!(tui.screen(), Screen::Game(0));
+    assert!(screen_text(&tui).contains("Memorise:"));
+    // Enter hides the chunk early; typing only counts once it is hidden.
+    press(&mut tui, [KeyCode::Enter]);
+    type_text(&mut tui, "31415");
+    press(&mut tui, [KeyCode::Enter]);
+    assert!(screen_text(&tui).contains("✓ +5 points"));
+
+    press(&mut tui, [KeyCode::Esc, KeyCode::Char('q')]);
+    assert!(!tui.is_running());
+    let data = Storage::new(path).load().unwrap();
+    assert_eq!(data.games["pi_challenge"].score, 5);
+    assert_eq!(data.history.len(), 1);
+}
+
+#[test]
+fn settings_and_scoreboard_are_reachable_from_the_menu() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("save.json");
+    let mut tui = Tui::new(Box::new(Storage::new(path.clone())), None);
+
+    press(&mut tui, [KeyCode::Up, KeyCode::Up, KeyCode::Enter]);
+    assert_eq!(tui.screen(), Screen::Scoreboard);
+    assert!(screen_text(&tui).contains("Score: 0 | Attempts: 0"));
+
+    press(&mut tui, [KeyCode::Esc, KeyCode::Up, KeyCode::Enter]);
+    assert_eq!(tui.screen(), Screen::Settings);
+    press(&mut tui, [KeyCode::Enter, KeyCode::Esc]);
+    assert_eq!(tui.screen(), Screen::MainMenu);
+    assert!(!Storage::new(path).load().unwrap().settings.audio_enabled);
+}